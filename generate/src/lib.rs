@@ -0,0 +1,438 @@
+//! Synthetic inputs of any size, in each day's format, for stress tests and benches.
+//!
+//! Generation only depends on the size and the seed. Inputs are written as they're
+//! generated, and the answers worked out along the way with a different algorithm
+//! than the day's, so they can be checked against:
+//!
+//! ```text
+//! cargo xt gen 7 --size 500 --seed 3 --out inputs/day7.txt
+//! ```
+//!
+//! A crate of its own, so that xtask doesn't build the solutions and what they
+//! depend on. The solutions' tests check them against the known answers.
+use std::{
+    io::{self, Write},
+    ops::RangeInclusive,
+};
+
+/// The answers known for a generated input, as `(part, answer)`. They're all numbers.
+pub type Known = Vec<(&'static str, u128)>;
+
+/// Writes an input of `size` to `out`, see [`GENERATORS`] for what the size is.
+pub type Generator = fn(size: usize, rng: &mut Rng, out: &mut dyn Write) -> io::Result<Known>;
+
+/// Every day's generator, with what its size counts.
+pub static GENERATORS: &[(&str, &str, Generator)] = &[
+    ("day1", "rotations", day1),
+    ("day3", "banks", day3),
+    ("day4", "rows and columns", day4),
+    ("day5", "ranges and IDs", day5),
+    ("day6", "problems", day6),
+    ("day7", "splitter rows", day7),
+    ("day8", "junction boxes", day8),
+];
+
+pub fn find(day: &str) -> Option<Generator> {
+    GENERATORS
+        .iter()
+        .find(|(name, _, _)| *name == day)
+        .map(|(_, _, generator)| *generator)
+}
+
+const GOLDEN: u64 = 0x9e37_79b9_7f4a_7c15;
+
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// SplitMix64, small and the same on every platform and version.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// Its own generator for item `key`, to generate it again without the others.
+    pub fn keyed(&self, key: u64) -> Self {
+        Rng::new(self.state ^ mix(key.wrapping_add(GOLDEN)))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GOLDEN);
+        mix(self.state)
+    }
+
+    /// Uniform in `0..n`, `n` must not be 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        start + self.below(end - start + 1)
+    }
+
+    /// `true` with probability `num / den`.
+    pub fn chance(&mut self, num: u64, den: u64) -> bool {
+        self.below(den) < num
+    }
+}
+
+/// Known answers, the ones that overflowed the part's type left out.
+fn known(answers: [(&'static str, Option<u128>); 2]) -> Known {
+    answers
+        .into_iter()
+        .filter_map(|(part, answer)| Some((part, answer?)))
+        .collect()
+}
+
+// Rotations of up to 999 clicks, the passes by 0 counted with a division per rotation
+fn day1(size: usize, rng: &mut Rng, out: &mut dyn Write) -> io::Result<Known> {
+    let (mut position, mut zeros, mut passes) = (50, 0u64, 0u64);
+    for _ in 0..size {
+        let clicks = rng.range(1..=999);
+        if rng.chance(1, 2) {
+            writeln!(out, "L{clicks}")?;
+            passes += match position {
+                0 => clicks / 100,
+                p if clicks >= p => (clicks - p) / 100 + 1,
+                _ => 0,
+            };
+            position = (position + 100 - clicks % 100) % 100;
+        } else {
+            writeln!(out, "R{clicks}")?;
+            passes += (position + clicks) / 100;
+            position = (position + clicks) % 100;
+        }
+        zeros += (position == 0) as u64;
+    }
+    Ok(known([
+        ("part_one", Some(zeros.into())),
+        ("part_two", Some(passes.into())),
+    ]))
+}
+
+// Greedily, the largest digit leaving enough of them for the rest
+fn largest_joltage(bank: &[u8], keep: usize) -> u64 {
+    let mut from = 0;
+    (0..keep).fold(0, |joltage, kept| {
+        let window = &bank[from..bank.len() - (keep - kept - 1)];
+        let max = *window.iter().max().expect("enough digits");
+        from += window.iter().position(|d| *d == max).expect("max is there") + 1;
+        joltage * 10 + (max - b'0') as u64
+    })
+}
+
+// Banks of 12 to 100 non-zero digits
+fn day3(size: usize, rng: &mut Rng, out: &mut dyn Write) -> io::Result<Known> {
+    let (mut one, mut two) = (Some(0u64), Some(0u128));
+    let mut bank = Vec::new();
+    for _ in 0..size {
+        bank.clear();
+        let len = rng.range(12..=100);
+        bank.extend((0..len).map(|_| b'0' + rng.range(1..=9) as u8));
+        bank.push(b'\n');
+        out.write_all(&bank)?;
+        bank.pop();
+        one = one.and_then(|one| one.checked_add(largest_joltage(&bank, 2)));
+        two = two.and_then(|two| two.checked_add(largest_joltage(&bank, 12) as u128));
+    }
+    Ok(known([
+        ("part_one", one.map(u128::from)),
+        ("part_two", two),
+    ]))
+}
+
+// A square of rolls, two thirds of the cells. The rolls are peeled off one at a time
+// in whatever order they become accessible, the grid is kept in memory for that.
+fn day4(size: usize, rng: &mut Rng, out: &mut dyn Write) -> io::Result<Known> {
+    let mut rolls = vec![false; size * size];
+    let mut line = Vec::with_capacity(size + 1);
+    for row in rolls.chunks_mut(size.max(1)) {
+        line.clear();
+        for cell in row {
+            *cell = rng.chance(2, 3);
+            line.push(if *cell { b'@' } else { b'.' });
+        }
+        line.push(b'\n');
+        out.write_all(&line)?;
+    }
+
+    let around = |idx: usize| {
+        let (x, y) = ((idx % size) as isize, (idx / size) as isize);
+        (-1..=1)
+            .flat_map(move |dy| (-1..=1).map(move |dx| (x + dx, y + dy)))
+            .filter(move |(xx, yy)| (*xx, *yy) != (x, y))
+            .filter(|(xx, yy)| (0..size as isize).contains(xx) && (0..size as isize).contains(yy))
+            .map(|(xx, yy)| yy as usize * size + xx as usize)
+    };
+    let mut counts: Vec<u8> = (0..rolls.len())
+        .map(|idx| around(idx).filter(|n| rolls[*n]).count() as u8)
+        .collect();
+    let mut queue: Vec<usize> = (0..rolls.len())
+        .filter(|idx| rolls[*idx] && counts[*idx] < 4)
+        .collect();
+    let accessible = queue.len();
+    let mut removed = 0;
+    while let Some(idx) = queue.pop() {
+        rolls[idx] = false;
+        removed += 1;
+        for next in around(idx) {
+            counts[next] -= 1;
+            // Queued once, when it first goes under 4
+            if rolls[next] && counts[next] == 3 {
+                queue.push(next);
+            }
+        }
+    }
+    Ok(known([
+        ("part_one", Some(accessible as u128)),
+        ("part_two", Some(removed as u128)),
+    ]))
+}
+
+// Fresh ranges of up to a trillion IDs below a quadrillion, then as many IDs, half of
+// them picked in a range
+fn day5(size: usize, rng: &mut Rng, out: &mut dyn Write) -> io::Result<Known> {
+    const MAX: u64 = 1_000_000_000_000_000;
+    const LEN: u64 = 1_000_000_000_000;
+    let mut ranges: Vec<(u64, u64)> = (0..size)
+        .map(|_| {
+            let start = rng.below(MAX);
+            (start, start + rng.below(LEN))
+        })
+        .collect();
+    for (start, end) in &ranges {
+        writeln!(out, "{start}-{end}")?;
+    }
+    writeln!(out)?;
+
+    let ids: Vec<u64> = (0..size)
+        .map(|_| match ranges.get(rng.below(2 * size as u64) as usize) {
+            Some((start, end)) => rng.range(*start..=*end),
+            None => rng.below(MAX),
+        })
+        .collect();
+    for id in &ids {
+        writeln!(out, "{id}")?;
+    }
+
+    ranges.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    let fresh = ids
+        .iter()
+        .filter(|id| {
+            let after = merged.partition_point(|(start, _)| start <= *id);
+            after > 0 && **id <= merged[after - 1].1
+        })
+        .count();
+    let total: u64 = merged.iter().map(|(start, end)| end - start + 1).sum();
+    Ok(known([
+        ("part_one", Some(fresh as u128)),
+        ("part_two", Some(total.into())),
+    ]))
+}
+
+const DAY6_ROWS: usize = 4;
+
+/// A day6 problem: its numbers as written, aligned on a side of its columns.
+struct Problem {
+    width: usize,
+    numbers: [Vec<u8>; DAY6_ROWS],
+    left: bool,
+    add: bool,
+}
+
+impl Problem {
+    // Up to 3 digits, so that products fit easily
+    fn new(rng: &mut Rng) -> Self {
+        let width = rng.range(1..=3) as usize;
+        let full = rng.below(DAY6_ROWS as u64) as usize;
+        let numbers = std::array::from_fn(|row| {
+            let len = if row == full {
+                width
+            } else {
+                rng.range(1..=width as u64) as usize
+            };
+            (0..len).map(|_| b'0' + rng.range(1..=9) as u8).collect()
+        });
+        Problem {
+            width,
+            numbers,
+            left: rng.chance(1, 2),
+            add: rng.chance(1, 2),
+        }
+    }
+
+    fn cell(&self, row: usize, col: usize) -> u8 {
+        let number = &self.numbers[row];
+        let col = if self.left {
+            col
+        } else {
+            (col + number.len()).wrapping_sub(self.width)
+        };
+        number.get(col).copied().unwrap_or(b' ')
+    }
+
+    fn solve(&self, mut numbers: impl Iterator<Item = u64>) -> Option<u64> {
+        if self.add {
+            numbers.try_fold(0u64, |acc, n| acc.checked_add(n))
+        } else {
+            numbers.try_fold(1u64, |acc, n| acc.checked_mul(n))
+        }
+    }
+
+    // Read as rows, then as columns
+    fn answers(&self) -> (Option<u64>, Option<u64>) {
+        let value = |digits: &mut dyn Iterator<Item = u8>| {
+            digits.fold(0, |n, d| n * 10 + (d - b'0') as u64)
+        };
+        let rows = self.numbers.iter().map(|n| value(&mut n.iter().copied()));
+        let cols = (0..self.width).map(|col| {
+            value(
+                &mut (0..DAY6_ROWS)
+                    .map(|row| self.cell(row, col))
+                    .filter(|c| *c != b' '),
+            )
+        });
+        (self.solve(rows), self.solve(cols))
+    }
+}
+
+// Each problem is generated again for every line, from its own generator
+fn day6(size: usize, rng: &mut Rng, out: &mut dyn Write) -> io::Result<Known> {
+    let problem = |idx: usize| Problem::new(&mut rng.keyed(idx as u64));
+    let mut line = Vec::new();
+    for row in 0..=DAY6_ROWS {
+        line.clear();
+        for idx in 0..size {
+            if idx > 0 {
+                line.push(b' ');
+            }
+            let problem = problem(idx);
+            if row == DAY6_ROWS {
+                line.push(if problem.add { b'+' } else { b'*' });
+                line.resize(line.len() + problem.width - 1, b' ');
+            } else {
+                line.extend((0..problem.width).map(|col| problem.cell(row, col)));
+            }
+        }
+        line.push(b'\n');
+        out.write_all(&line)?;
+    }
+
+    let (mut one, mut two) = (Some(0u64), Some(0u64));
+    for idx in 0..size {
+        let (rows, cols) = problem(idx).answers();
+        one = one.zip(rows).and_then(|(total, n)| total.checked_add(n));
+        two = two.zip(cols).and_then(|(total, n)| total.checked_add(n));
+    }
+    Ok(known([
+        ("part_one", one.map(u128::from)),
+        ("part_two", two.map(u128::from)),
+    ]))
+}
+
+// Splitter rows every other line, a splitter on half of the cells the beam could
+// reach. Timelines are counted forward, per column.
+fn day7(size: usize, rng: &mut Rng, out: &mut dyn Write) -> io::Result<Known> {
+    let width = 2 * size + 3;
+    let start = size + 1;
+    let mut line = vec![b'.'; width + 1];
+    line[width] = b'\n';
+    line[start] = b'S';
+    out.write_all(&line)?;
+    line[start] = b'.';
+
+    let mut timelines = vec![Some(0u64); width];
+    timelines[start] = Some(1);
+    let mut splits = 0u64;
+    for row in 0..size {
+        out.write_all(&line)?;
+        let mut splitters = line.clone();
+        let mut next = vec![Some(0u64); width];
+        let add = |to: &mut Option<u64>, n: Option<u64>| {
+            *to = to.zip(n).and_then(|(to, n)| to.checked_add(n));
+        };
+        for x in (start - row..=start + row).step_by(2) {
+            if rng.chance(1, 2) {
+                splitters[x] = b'^';
+            }
+        }
+        for (x, count) in timelines.iter().enumerate() {
+            if *count == Some(0) {
+                continue;
+            }
+            if splitters[x] == b'^' {
+                splits += 1;
+                add(&mut next[x - 1], *count);
+                add(&mut next[x + 1], *count);
+            } else {
+                add(&mut next[x], *count);
+            }
+        }
+        out.write_all(&splitters)?;
+        timelines = next;
+    }
+    out.write_all(&line)?;
+
+    let total = timelines
+        .into_iter()
+        .try_fold(0u64, |total, n| total.checked_add(n?));
+    Ok(known([
+        ("part_one", Some(splits.into())),
+        ("part_two", total.map(u128::from)),
+    ]))
+}
+
+// Coordinates below 100000 like the real input. Boxes are connected in order of their
+// exact squared distance, but on large inputs some pairs are the same distance apart,
+// and which of them comes first depends on the day's tie-break: no known answers.
+fn day8(size: usize, rng: &mut Rng, out: &mut dyn Write) -> io::Result<Known> {
+    for _ in 0..size {
+        let [x, y, z] = std::array::from_fn(|_| rng.below(100_000));
+        writeln!(out, "{x},{y},{z}")?;
+    }
+    Ok(Known::new())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn generate(day: &str, size: usize, seed: u64) -> (String, Known) {
+        let mut out = Vec::new();
+        let known = find(day).expect("a generator")(size, &mut Rng::new(seed), &mut out)
+            .expect("writes to a Vec");
+        (String::from_utf8(out).expect("ASCII"), known)
+    }
+
+    #[test]
+    fn generate_deterministic() {
+        for (day, _, _) in GENERATORS {
+            assert_eq!(generate(day, 20, 7), generate(day, 20, 7), "{day}");
+            assert_ne!(generate(day, 20, 7).0, generate(day, 20, 8).0, "{day}");
+            let known = generate(day, 20, 7).1.len();
+            assert_eq!(known, if *day == "day8" { 0 } else { 2 }, "{day}");
+        }
+        assert!(find("day2").is_none());
+    }
+
+    #[test]
+    fn generate_joltage() {
+        assert_eq!(largest_joltage(b"818181911112111", 2), 92);
+        assert_eq!(largest_joltage(b"818181911112111", 12), 888911112111);
+    }
+}
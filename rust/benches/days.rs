@@ -0,0 +1,149 @@
+//! Every day's parsers and parts, on the example files and on `inputs/dayN.txt`.
+//!
+//! Days without an input file are only benched on their examples. Criterion keeps
+//! baselines around:
+//!
+//! ```text
+//! cargo bench --bench days -- --save-baseline before
+//! # ... tune things ...
+//! cargo bench --bench days -- --baseline before
+//! ```
+//!
+//! Memoized functions start from empty caches on every iteration. Changes below the
+//! noise threshold (`AOC_BENCH_THRESHOLD`, 5% by default) aren't reported as
+//! regressions.
+//!
+//! Day4's neighbour counts are also benched on their own, the hash set version against
+//! the bit grid kernels.
+use std::{collections::HashSet, hint::black_box, path::Path, time::Duration};
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+const THRESHOLD: f64 = 0.05;
+
+/// `(name, input)` for every example of `day`, and its real input if downloaded.
+fn inputs(day: &str) -> Vec<(String, String)> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    let mut examples: Vec<_> = std::fs::read_dir(root.join("examples").join(day))
+        .into_iter()
+        .flatten()
+        .map(|entry| entry.expect("example entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    examples.sort();
+    let mut inputs: Vec<_> = examples
+        .iter()
+        .map(|path| {
+            let name = path
+                .file_stem()
+                .expect("stem")
+                .to_string_lossy()
+                .into_owned();
+            let input = std::fs::read_to_string(path).expect("Failed to read example");
+            (name, input)
+        })
+        .collect();
+
+    let file = format!("inputs/{day}.txt");
+    match [root.join(&file), root.join("..").join(&file)]
+        .iter()
+        .find_map(|path| std::fs::read_to_string(path).ok())
+    {
+        Some(input) => inputs.push(("input".to_string(), input)),
+        None => eprintln!("{day}: no {file}, skipping the real input"),
+    }
+    inputs
+}
+
+macro_rules! bench_day {
+    ($c:expr, $day:ident, [$($call:ident),*]) => {{
+        use aoc25::{days::$day::*, input::Input, runner::FromInput};
+        for (name, input) in inputs(stringify!($day)) {
+            let mut group = $c.benchmark_group(format!("{}/{name}", stringify!($day)));
+            $(
+                // Cold caches every time, as in the runner
+                group.bench_function(stringify!($call), |b| {
+                    b.iter_batched(
+                        aoc25::cache::reset_all,
+                        |()| $call(black_box(FromInput::from_input(&Input::from(input.as_str())))),
+                        BatchSize::SmallInput,
+                    )
+                });
+            )*
+            group.finish();
+        }
+    }};
+}
+
+fn days(c: &mut Criterion) {
+    bench_day!(c, day1, [parse, part_one, part_two]);
+    bench_day!(c, day3, [parse, part_one, part_two]);
+    bench_day!(c, day4, [parse_grid, part_one, part_two]);
+    bench_day!(c, day5, [parse, part_one, part_two]);
+    bench_day!(c, day6, [parse, parse_columns, part_one, part_two]);
+    bench_day!(c, day7, [parse, part_one, part_two]);
+    bench_day!(c, day8, [parse, part_one, part_two]);
+}
+
+// The hash set version day4 had before its bit grid
+fn paper_rolls(data: &HashSet<(i32, i32)>) -> usize {
+    data.iter()
+        .filter(|(x, y)| {
+            let around = (y - 1..=y + 1).flat_map(|yy| (x - 1..=x + 1).map(move |xx| (xx, yy)));
+            around
+                .filter(|pos| *pos != (*x, *y) && data.contains(pos))
+                .count()
+                < 4
+        })
+        .count()
+}
+
+fn day4_neighbours(c: &mut Criterion) {
+    use aoc25::{
+        bits::{kernel, BitGrid},
+        days::day4,
+    };
+    for (name, input) in inputs("day4") {
+        let grid = day4::parse_grid(&input).expect("valid input");
+        let data: HashSet<(i32, i32)> = grid
+            .iter()
+            .filter(|(_, roll)| **roll)
+            .map(|((x, y), _)| (x as i32, y as i32))
+            .collect();
+        let rolls = BitGrid::from(&grid);
+
+        let mut group = c.benchmark_group(format!("day4/neighbours/{name}"));
+        group.bench_function("paper_rolls", |b| b.iter(|| paper_rolls(black_box(&data))));
+        group.bench_function("kernel", |b| {
+            b.iter(|| day4::accessible(black_box(&rolls)).count())
+        });
+        group.bench_function("scalar", |b| {
+            b.iter(|| {
+                let mut accessible =
+                    kernel::neighbour_counts_scalar(black_box(&rolls)).fewer_than(4);
+                accessible.intersection_with(&rolls);
+                accessible.count()
+            })
+        });
+        group.finish();
+    }
+}
+
+fn config() -> Criterion {
+    let threshold = std::env::var("AOC_BENCH_THRESHOLD")
+        .ok()
+        .and_then(|t| t.parse().ok())
+        .unwrap_or(THRESHOLD);
+    Criterion::default()
+        .noise_threshold(threshold)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2))
+}
+
+criterion_group! {
+    name = benches;
+    config = config();
+    targets = days, day4_neighbours
+}
+criterion_main!(benches);
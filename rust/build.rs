@@ -0,0 +1,60 @@
+//! Generates one test per example file and part, see `src/examples.rs`.
+use std::{fmt::Write, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=examples");
+    println!("cargo:rerun-if-changed=src/days");
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR");
+    let out_dir = Path::new(&out_dir).join("examples");
+    fs::create_dir_all(&out_dir).expect("Failed to create examples output dir");
+
+    let days = fs::read_dir("src/days").expect("Failed to read src/days");
+    for day in days {
+        let day = day.expect("day entry").path();
+        let Some(day) = day.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let tests = tests(&Path::new("examples").join(day));
+        fs::write(out_dir.join(format!("{day}.rs")), tests).expect("Failed to write tests");
+    }
+}
+
+fn tests(dir: &Path) -> String {
+    let mut tests = String::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return tests;
+    };
+
+    let mut expected: Vec<_> = entries
+        .map(|e| e.expect("example entry").path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "expected"))
+        .collect();
+    expected.sort();
+
+    for expected in expected {
+        let input = expected.with_extension("txt");
+        assert!(input.exists(), "{} has no input file", expected.display());
+        let input = fs::canonicalize(input).expect("canonical input path");
+        let stem = expected.file_stem().expect("stem").to_string_lossy();
+        let name: String = stem
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+
+        let answers = fs::read_to_string(&expected).expect("Failed to read expected answers");
+        for line in answers.lines().filter(|l| !l.trim().is_empty()) {
+            let (part, answer) = line
+                .split_once(':')
+                .unwrap_or_else(|| panic!("{}: expected `part: answer`", expected.display()));
+            let part = part.trim();
+            writeln!(
+                tests,
+                "#[test]\nfn {name}_{part}() {{\n    crate::examples::check({part}, include_str!({input:?}), {answer:?});\n}}",
+                answer = answer.trim(),
+            )
+            .expect("write to string");
+        }
+    }
+    tests
+}
@@ -0,0 +1,161 @@
+//! Counting global allocator, behind the `alloc-profile` feature.
+//!
+//! With the feature on, the runner prints the allocations of every part next to its
+//! duration, and tests can check that a hot path doesn't allocate at all:
+//!
+//! ```ignore
+//! #[cfg(feature = "alloc-profile")]
+//! #[test]
+//! fn hot_path() {
+//!     alloc::assert_no_alloc(|| dial.passes(1000, 0));
+//! }
+//! ```
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub allocations: usize,
+    pub bytes: usize,
+    /// Highest heap usage over the one at the start of the measure
+    pub peak: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} KiB allocated, {} KiB peak",
+            self.allocations,
+            self.bytes.div_ceil(1024),
+            self.peak.div_ceil(1024),
+        )
+    }
+}
+
+#[cfg(feature = "alloc-profile")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    pub static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    pub static BYTES: AtomicUsize = AtomicUsize::new(0);
+    pub static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    pub static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    thread_local! {
+        // const, so that reading it never allocates
+        pub static THREAD_ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    pub struct Counting;
+
+    impl Counting {
+        fn grow(&self, size: usize) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(size, Ordering::Relaxed);
+            let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+            PEAK.fetch_max(current, Ordering::Relaxed);
+            let _ = THREAD_ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        }
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            self.grow(layout.size());
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            self.grow(layout.size());
+            unsafe { System.alloc_zeroed(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            self.grow(new_size);
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+}
+
+/// Runs `f`, and counts what the whole process allocated meanwhile.
+///
+/// The stats are `None` without the `alloc-profile` feature.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Stats>) {
+    #[cfg(feature = "alloc-profile")]
+    {
+        use std::sync::atomic::Ordering;
+
+        use counting::*;
+
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let bytes = BYTES.load(Ordering::Relaxed);
+        let start = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(start, Ordering::Relaxed);
+
+        let result = f();
+
+        let stats = Stats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - bytes,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(start),
+        };
+        (result, Some(stats))
+    }
+
+    #[cfg(not(feature = "alloc-profile"))]
+    (f(), None)
+}
+
+/// Number of allocations made by the current thread while running `f`.
+#[cfg(feature = "alloc-profile")]
+pub fn count_in_thread<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = counting::THREAD_ALLOCATIONS.with(|count| count.get());
+    let result = f();
+    let after = counting::THREAD_ALLOCATIONS.with(|count| count.get());
+    (result, after - before)
+}
+
+/// Panics if `f` allocates on the current thread.
+#[cfg(feature = "alloc-profile")]
+pub fn assert_no_alloc<T>(f: impl FnOnce() -> T) -> T {
+    let (result, allocations) = count_in_thread(f);
+    assert_eq!(allocations, 0, "expected no allocation");
+    result
+}
+
+#[cfg(all(test, feature = "alloc-profile"))]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn alloc_count() {
+        let (v, allocations) = count_in_thread(|| vec![1u64; 1000]);
+        assert_eq!(allocations, 1);
+
+        let (_, stats) = measure(|| v.clone());
+        let stats = stats.expect("enabled");
+        assert!(stats.allocations >= 1);
+        assert!(stats.bytes >= 8000);
+        assert!(stats.peak >= 8000);
+
+        assert_no_alloc(|| v.iter().sum::<u64>());
+    }
+
+    #[test]
+    #[should_panic(expected = "expected no allocation")]
+    fn alloc_assert_no_alloc() {
+        assert_no_alloc(|| vec![1]);
+    }
+}
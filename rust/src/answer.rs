@@ -0,0 +1,218 @@
+//! The one form every part's result takes, see [`Answer`].
+use std::fmt;
+
+use crate::{
+    grid::Grid,
+    num::{BigInt, BigUint},
+    ocr,
+};
+
+/// What a part answers. Parts keep returning whatever is natural to them, the
+/// [`crate::parts!`] macro converts it.
+///
+/// Integers are `Int` whenever they fit an `i128`, whatever type the part returned, so
+/// that equal answers are equal values. Text a part returns is kept as is, expected
+/// answers are checked with [`Answer::matches`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Big(BigInt),
+    Text(String),
+    /// ASCII art, like block letters drawn with `#` and `.`, see [`Answer::decoded`]
+    Art(Grid<char>),
+}
+
+impl Answer {
+    /// Reads back a displayed answer, e.g. from an expected answers file.
+    ///
+    /// Multi-line text is art when all its lines are of the same length.
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        if text.contains('\n') {
+            if let Ok(grid) = Grid::try_parse(text, Ok) {
+                return Answer::Art(grid);
+            }
+        } else if let Ok(n) = text.parse::<BigInt>() {
+            return n.into();
+        }
+        Answer::Text(text.to_string())
+    }
+
+    /// Whether this is the answer displayed as `expected`, e.g. in an expected answers
+    /// file. Art matches the letters it draws, and text its exact display too, so that
+    /// a part answering the text `007` matches `007`.
+    pub fn matches(&self, expected: &str) -> bool {
+        let actual = self.clone().decoded();
+        if let Answer::Text(text) = &actual {
+            if text == expected.trim() {
+                return true;
+            }
+        }
+        actual == Answer::parse(expected).decoded()
+    }
+
+    /// The letters drawn by art, see [`ocr`].
+    pub fn ocr(&self) -> Option<String> {
+        match self {
+            Answer::Art(grid) => ocr::read_grid(&grid.map(|c| *c == '#')),
+            _ => None,
+        }
+    }
+
+    /// Art as the text it reads, what gets stored and compared.
+    pub fn decoded(self) -> Self {
+        match self.ocr() {
+            Some(text) => Answer::Text(text),
+            None => self,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Big(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Art(grid) => {
+                for y in 0..grid.height() {
+                    if y > 0 {
+                        writeln!(f)?;
+                    }
+                    for x in 0..grid.width() {
+                        write!(f, "{}", grid[(x, y)])?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    Answer::Int(n.into())
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64);
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::Int(n as i128)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Int(n as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Big(n.into()),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        match i128::try_from(&n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Big(n),
+        }
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Self {
+        BigInt::from(n).into()
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl From<Grid<char>> for Answer {
+    fn from(grid: Grid<char>) -> Self {
+        Answer::Art(grid)
+    }
+}
+
+/// Lit cells as `#`, the others as `.`
+impl From<Grid<bool>> for Answer {
+    fn from(grid: Grid<bool>) -> Self {
+        Answer::Art(grid.map(|lit| if *lit { '#' } else { '.' }))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_canonical() {
+        assert_eq!(Answer::from(42u64), Answer::from(42i32));
+        assert_eq!(Answer::from(42usize), Answer::parse(" 0042\n"));
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert!(matches!(Answer::from(u128::MAX), Answer::Big(_)));
+        assert_eq!(Answer::from(BigInt::from(-7)), Answer::Int(-7));
+        assert_eq!(Answer::from("ABC"), Answer::Text("ABC".to_string()));
+    }
+
+    #[test]
+    fn answer_text() {
+        // Returned text is never read as a number or art
+        let zeros = Answer::from("007");
+        assert_eq!(zeros, Answer::Text("007".to_string()));
+        assert_eq!(zeros.to_string(), "007");
+        assert!(matches!(
+            Answer::from("12\n34".to_string()),
+            Answer::Text(_)
+        ));
+
+        assert!(zeros.matches("007"));
+        assert!(!zeros.matches("7"));
+        assert!(Answer::from(7).matches("007"));
+        assert!(!Answer::from(8).matches("007"));
+    }
+
+    #[test]
+    fn answer_art() {
+        let art = ".#.\n#.#";
+        let grid = Grid::parse(art, |c| c == '#');
+        let answer = Answer::from(grid);
+        assert_eq!(answer, Answer::parse(&format!("\n{art}\n")));
+        assert_eq!(answer.to_string(), art);
+        assert!(matches!(Answer::parse("ab\nc"), Answer::Text(_)));
+        assert_eq!(answer.clone().decoded(), answer);
+    }
+
+    #[test]
+    fn answer_ocr() {
+        let art = "#..#.###\n#..#..#.\n####..#.\n#..#..#.\n#..#..#.\n#..#.###";
+        let answer = Answer::parse(art);
+        assert_eq!(answer.ocr().as_deref(), Some("HI"));
+        assert!(answer.matches("HI"));
+        assert!(answer.matches(art));
+        assert_eq!(answer.decoded(), Answer::parse("HI"));
+    }
+}
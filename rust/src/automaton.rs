@@ -0,0 +1,122 @@
+use crate::grid::{Grid, Neighbours, Pos};
+
+/// Synchronous cellular automaton over a [`Grid`].
+///
+/// Only the cells around the ones that changed during a generation are looked at again
+/// in the next one, so a run costs roughly the number of changes rather than
+/// `generations * grid size`.
+pub struct Automaton<'a> {
+    neighbourhood: &'a [(isize, isize)],
+    max_steps: usize,
+}
+
+pub struct Run<T> {
+    pub grid: Grid<T>,
+    /// Number of cells that changed, for each generation.
+    pub generations: Vec<usize>,
+    /// `false` if the run stopped because of `max_steps`.
+    pub fixed_point: bool,
+}
+
+impl<'a> Automaton<'a> {
+    pub fn new(neighbourhood: &'a [(isize, isize)], max_steps: usize) -> Self {
+        Automaton {
+            neighbourhood,
+            max_steps,
+        }
+    }
+
+    /// Runs `rule` until nothing changes anymore, or `max_steps` generations went by.
+    ///
+    /// `rule` gets a cell and its neighbours, and returns `Some` with the new value
+    /// when the cell changes. Returning `Some` for a cell that stays the same counts
+    /// as a change.
+    pub fn run<T, R>(&self, grid: Grid<T>, rule: R) -> Run<T>
+    where
+        R: FnMut(&T, Neighbours<'_, T>) -> Option<T>,
+    {
+        self.run_inspect(grid, rule, |_, _| {})
+    }
+
+    /// Same as [`Automaton::run`], calling `inspect` with the grid and the cells that
+    /// changed after every generation.
+    pub fn run_inspect<T, R, I>(&self, mut grid: Grid<T>, mut rule: R, mut inspect: I) -> Run<T>
+    where
+        R: FnMut(&T, Neighbours<'_, T>) -> Option<T>,
+        I: FnMut(&Grid<T>, &[Pos]),
+    {
+        let mut queued = Grid::new(grid.width(), grid.height(), true);
+        let mut worklist: Vec<Pos> = grid.positions().collect();
+        let mut generations = Vec::new();
+
+        while generations.len() < self.max_steps {
+            let changes: Vec<(Pos, T)> = worklist
+                .drain(..)
+                .filter_map(|pos| {
+                    queued[pos] = false;
+                    rule(&grid[pos], grid.neighbours(pos, self.neighbourhood)).map(|v| (pos, v))
+                })
+                .collect();
+
+            if changes.is_empty() {
+                return Run {
+                    grid,
+                    generations,
+                    fixed_point: true,
+                };
+            }
+            generations.push(changes.len());
+
+            let mut changed = Vec::with_capacity(changes.len());
+            for (pos, value) in changes {
+                grid[pos] = value;
+                changed.push(pos);
+                for p in std::iter::once(pos).chain(grid.adjacent(pos, self.neighbourhood)) {
+                    if !queued[p] {
+                        queued[p] = true;
+                        worklist.push(p);
+                    }
+                }
+            }
+            inspect(&grid, &changed);
+        }
+
+        Run {
+            grid,
+            generations,
+            fixed_point: false,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::grid::MOORE;
+
+    fn life(alive: &bool, neighbours: Neighbours<'_, bool>) -> Option<bool> {
+        let count = neighbours.filter(|n| **n).count();
+        let next = count == 3 || (*alive && count == 2);
+        (next != *alive).then_some(next)
+    }
+
+    #[test]
+    fn automaton_fixed_point() {
+        let block = Grid::parse("....\n.##.\n.#..\n....", |c| c == '#');
+        let run = Automaton::new(&MOORE, 10).run(block, life);
+        assert!(run.fixed_point);
+        assert_eq!(run.generations, vec![1]);
+        assert_eq!(
+            run.grid,
+            Grid::parse("....\n.##.\n.##.\n....", |c| c == '#')
+        );
+    }
+
+    #[test]
+    fn automaton_max_steps() {
+        let blinker = Grid::parse(".....\n..#..\n..#..\n..#..\n.....", |c| c == '#');
+        let run = Automaton::new(&MOORE, 5).run(blinker, life);
+        assert!(!run.fixed_point);
+        assert_eq!(run.generations, vec![4; 5]);
+    }
+}
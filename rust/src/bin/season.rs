@@ -0,0 +1,3 @@
+fn main() {
+    aoc25::season::main();
+}
@@ -0,0 +1,347 @@
+//! Dense sets of small integers and of grid positions, one bit each.
+use std::ops::{BitAnd, BitOr, Sub};
+
+use crate::grid::{Grid, Pos};
+
+pub mod kernel;
+
+const BITS: usize = u64::BITS as usize;
+
+/// Indices of the set bits, in order.
+fn ones(words: &[u64]) -> impl Iterator<Item = usize> + '_ {
+    words.iter().enumerate().flat_map(|(idx, word)| {
+        let mut word = *word;
+        std::iter::from_fn(move || {
+            (word != 0).then(|| {
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                idx * BITS + bit
+            })
+        })
+    })
+}
+
+/// Set of `usize`, growing to fit the largest one.
+#[derive(Debug, Clone, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        BitSet::default()
+    }
+
+    /// `true` if `value` wasn't in the set.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / BITS, 1 << (value % BITS));
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let inserted = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        inserted
+    }
+
+    /// `true` if `value` was in the set.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let contained = self.contains(value);
+        if contained {
+            self.words[value / BITS] &= !(1 << (value % BITS));
+        }
+        contained
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.words
+            .get(value / BITS)
+            .is_some_and(|word| word & (1 << (value % BITS)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// The values, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        ones(&self.words)
+    }
+
+    fn zip_with(&self, other: &BitSet, f: impl Fn(u64, u64) -> u64) -> BitSet {
+        let len = self.words.len().max(other.words.len());
+        let word = |words: &[u64], idx| words.get(idx).copied().unwrap_or(0);
+        BitSet {
+            words: (0..len)
+                .map(|idx| f(word(&self.words, idx), word(&other.words, idx)))
+                .collect(),
+        }
+    }
+
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &BitSet) -> BitSet {
+        self.zip_with(other, |a, b| a & !b)
+    }
+}
+
+/// Sets are equal whatever room they grew.
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.zip_with(other, |a, b| a ^ b).is_empty()
+    }
+}
+
+impl Eq for BitSet {}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+impl BitOr for &BitSet {
+    type Output = BitSet;
+
+    fn bitor(self, other: &BitSet) -> BitSet {
+        self.union(other)
+    }
+}
+
+impl BitAnd for &BitSet {
+    type Output = BitSet;
+
+    fn bitand(self, other: &BitSet) -> BitSet {
+        self.intersection(other)
+    }
+}
+
+impl Sub for &BitSet {
+    type Output = BitSet;
+
+    fn sub(self, other: &BitSet) -> BitSet {
+        self.difference(other)
+    }
+}
+
+/// A [`Grid<bool>`] packed row by row, each row starting on a new word.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    /// Words per row
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(BITS);
+        BitGrid {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> bool {
+        assert!(
+            x < self.width && y < self.height,
+            "{:?} out of bounds",
+            (x, y)
+        );
+        self.words[y * self.stride + x / BITS] & (1 << (x % BITS)) != 0
+    }
+
+    pub fn set(&mut self, (x, y): Pos, value: bool) {
+        assert!(
+            x < self.width && y < self.height,
+            "{:?} out of bounds",
+            (x, y)
+        );
+        let (word, bit) = (y * self.stride + x / BITS, 1 << (x % BITS));
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+    }
+
+    /// The words of row `y`, the bits past the width are always 0.
+    pub fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.stride..(y + 1) * self.stride]
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// The set positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.height).flat_map(move |y| ones(self.row(y)).map(move |x| (x, y)))
+    }
+
+    /// Set cells among the 3 of row `y` centered on `x`.
+    fn row_count(&self, y: usize, x: usize) -> u32 {
+        let (from, to) = (x.saturating_sub(1), (x + 1).min(self.width - 1));
+        let row = self.row(y);
+        (from / BITS..=to / BITS)
+            .map(|word| {
+                let lo = from.max(word * BITS) - word * BITS;
+                let hi = to.min(word * BITS + BITS - 1) - word * BITS;
+                let mask = (u64::MAX >> (BITS - 1 - (hi - lo))) << lo;
+                (row[word] & mask).count_ones()
+            })
+            .sum()
+    }
+
+    /// Set cells among the 8 around `pos`.
+    pub fn neighbour_count(&self, (x, y): Pos) -> u32 {
+        let rows = y.saturating_sub(1)..=(y + 1).min(self.height - 1);
+        let count: u32 = rows.map(|yy| self.row_count(yy, x)).sum();
+        count - self.get((x, y)) as u32
+    }
+
+    fn zip_with(&mut self, other: &BitGrid, f: impl Fn(u64, u64) -> u64) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grids of different sizes"
+        );
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word = f(*word, *other);
+        }
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    pub fn intersection_with(&mut self, other: &BitGrid) {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    pub fn difference_with(&mut self, other: &BitGrid) {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        let mut grid = Grid::new(self.width, self.height, false);
+        for pos in self.iter() {
+            grid[pos] = true;
+        }
+        grid
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = BitGrid::new(grid.width(), grid.height());
+        for (pos, set) in grid.iter() {
+            if *set {
+                bits.set(pos, true);
+            }
+        }
+        bits
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::grid::MOORE;
+    use proptest::prelude::*;
+
+    #[test]
+    fn bits_set() {
+        let mut a: BitSet = [1, 5, 64, 200].into_iter().collect();
+        assert!(a.contains(64) && !a.contains(63) && !a.contains(1000));
+        assert!(!a.insert(5));
+        assert!(a.remove(200) && !a.remove(200));
+        assert_eq!(a.iter().collect::<Vec<_>>(), [1, 5, 64]);
+        assert_eq!(a.len(), 3);
+
+        let b: BitSet = [5, 6, 130].into_iter().collect();
+        assert_eq!((&a | &b).iter().collect::<Vec<_>>(), [1, 5, 6, 64, 130]);
+        assert_eq!((&a & &b).iter().collect::<Vec<_>>(), [5]);
+        assert_eq!((&a - &b).iter().collect::<Vec<_>>(), [1, 64]);
+        // Not the same number of words
+        assert_eq!(&b - &b, BitSet::new());
+        assert!((&b - &b).is_empty());
+    }
+
+    #[test]
+    fn bits_grid() {
+        let grid = Grid::parse("#.#\n.##\n...", |c| c == '#');
+        let mut bits = BitGrid::from(&grid);
+        assert_eq!(bits.count(), 4);
+        assert_eq!(
+            bits.iter().collect::<Vec<_>>(),
+            [(0, 0), (2, 0), (1, 1), (2, 1)]
+        );
+        assert_eq!(bits.neighbour_count((1, 0)), 4);
+        assert_eq!(bits.neighbour_count((0, 2)), 1);
+
+        let mut other = BitGrid::new(3, 3);
+        other.set((2, 0), true);
+        other.set((2, 2), true);
+        bits.difference_with(&other);
+        assert_eq!(bits.count(), 3);
+        bits.union_with(&other);
+        assert_eq!(bits.count(), 5);
+        bits.intersection_with(&other);
+        assert_eq!(bits, other);
+        assert_eq!(BitGrid::from(&other.to_grid()), other);
+    }
+
+    proptest! {
+        #[test]
+        fn bits_neighbour_count(
+            (width, cells) in (1usize..150).prop_flat_map(|w| (Just(w), prop::collection::vec(any::<bool>(), w..w * 5)))
+        ) {
+            let height = cells.len() / width;
+            let mut grid = Grid::new(width, height, false);
+            for (pos, cell) in grid.positions().zip(&cells).collect::<Vec<_>>() {
+                grid[pos] = *cell;
+            }
+            let bits = BitGrid::from(&grid);
+            for pos in grid.positions() {
+                let expected = grid.neighbours(pos, &MOORE).filter(|n| **n).count();
+                prop_assert_eq!(bits.neighbour_count(pos) as usize, expected);
+                prop_assert_eq!(bits.get(pos), grid[pos]);
+            }
+        }
+    }
+}
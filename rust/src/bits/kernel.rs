@@ -0,0 +1,176 @@
+//! Neighbour counts of every cell of a [`BitGrid`] at once.
+//!
+//! The word kernel handles 64 cells per operation: the 8 neighbours of a word are the
+//! words above, below and on its row shifted by one bit, and they're added up
+//! bit-sliced, each bit of the counts in its own word. The scalar version counts cell
+//! by cell with [`BitGrid::neighbour_count`], it's the reference of the kernel.
+use super::{BitGrid, BITS};
+use crate::grid::Pos;
+
+/// Counts go up to 8, so 4 bits each.
+const PLANES: usize = 4;
+
+/// The count of 8-neighbours of every cell, set or not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NeighbourCounts {
+    width: usize,
+    height: usize,
+    stride: usize,
+    /// Bit `i` of the counts of the cells of a word is in `planes[word][i]`
+    planes: Vec<[u64; PLANES]>,
+}
+
+impl NeighbourCounts {
+    pub fn get(&self, (x, y): Pos) -> u32 {
+        assert!(
+            x < self.width && y < self.height,
+            "{:?} out of bounds",
+            (x, y)
+        );
+        let planes = self.planes[y * self.stride + x / BITS];
+        (0..PLANES)
+            .map(|i| (((planes[i] >> (x % BITS)) & 1) as u32) << i)
+            .sum()
+    }
+
+    /// The cells with fewer than `n` neighbours.
+    pub fn fewer_than(&self, n: u32) -> BitGrid {
+        let mut grid = BitGrid::new(self.width, self.height);
+        let last = last_word_mask(self.width);
+        for (idx, planes) in self.planes.iter().enumerate() {
+            let mut word = 0;
+            for count in 0..n.min(1 << PLANES) {
+                word |= (0..PLANES).fold(u64::MAX, |eq, i| match (count >> i) & 1 {
+                    1 => eq & planes[i],
+                    _ => eq & !planes[i],
+                });
+            }
+            // Keeps the bits past the width clear
+            if idx % self.stride == self.stride - 1 {
+                word &= last;
+            }
+            grid.words[idx] = word;
+        }
+        grid
+    }
+}
+
+/// The bits of the last word of a row that are in the grid.
+fn last_word_mask(width: usize) -> u64 {
+    match width % BITS {
+        0 => u64::MAX,
+        bits => (1 << bits) - 1,
+    }
+}
+
+/// Adds `bits` to the bit-sliced counts, one full adder per plane.
+fn add(planes: &mut [u64; PLANES], bits: u64) {
+    let mut carry = bits;
+    for plane in planes.iter_mut() {
+        let next = *plane & carry;
+        *plane ^= carry;
+        carry = next;
+    }
+}
+
+/// Word kernel, see the [module](self) docs.
+pub fn neighbour_counts(grid: &BitGrid) -> NeighbourCounts {
+    let stride = grid.stride;
+    let word = |y: Option<usize>, w: Option<usize>| match (y, w) {
+        (Some(y), Some(w)) if y < grid.height && w < stride => grid.words[y * stride + w],
+        _ => 0,
+    };
+    // Bit x of the result is bit x - 1 of the row, or x + 1
+    let west = |y, w: usize| (word(y, Some(w)) << 1) | (word(y, w.checked_sub(1)) >> (BITS - 1));
+    let east = |y, w: usize| (word(y, Some(w)) >> 1) | (word(y, Some(w + 1)) << (BITS - 1));
+
+    let mut planes = vec![[0; PLANES]; grid.words.len()];
+    for y in 0..grid.height {
+        let rows = [y.checked_sub(1), Some(y), Some(y + 1)];
+        for w in 0..stride {
+            let counts = &mut planes[y * stride + w];
+            for (idx, row) in rows.into_iter().enumerate() {
+                add(counts, west(row, w));
+                add(counts, east(row, w));
+                // Not the cell itself
+                if idx != 1 {
+                    add(counts, word(row, Some(w)));
+                }
+            }
+        }
+        // Shifted west, the last cell of the row counts past the width
+        if let Some(last) = ((y + 1) * stride).checked_sub(1) {
+            for plane in planes[last].iter_mut() {
+                *plane &= last_word_mask(grid.width);
+            }
+        }
+    }
+    NeighbourCounts {
+        width: grid.width,
+        height: grid.height,
+        stride,
+        planes,
+    }
+}
+
+/// Scalar fallback, see the [module](self) docs.
+pub fn neighbour_counts_scalar(grid: &BitGrid) -> NeighbourCounts {
+    let mut planes = vec![[0; PLANES]; grid.words.len()];
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            let count = grid.neighbour_count((x, y));
+            let planes = &mut planes[y * grid.stride + x / BITS];
+            for (i, plane) in planes.iter_mut().enumerate() {
+                *plane |= (((count >> i) & 1) as u64) << (x % BITS);
+            }
+        }
+    }
+    NeighbourCounts {
+        width: grid.width,
+        height: grid.height,
+        stride: grid.stride,
+        planes,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use proptest::prelude::*;
+
+    #[test]
+    fn kernel_counts() {
+        let grid = BitGrid::from(&Grid::parse("###\n#.#\n###", |c| c == '#'));
+        let counts = neighbour_counts(&grid);
+        assert_eq!(counts.get((1, 1)), 8);
+        assert_eq!(counts.get((0, 0)), 2);
+        assert_eq!(counts.get((1, 0)), 4);
+        let few = counts.fewer_than(4);
+        assert_eq!(
+            few.iter().collect::<Vec<_>>(),
+            [(0, 0), (2, 0), (0, 2), (2, 2)]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn kernel_against_scalar(
+            (width, cells) in (1usize..200).prop_flat_map(|w| (Just(w), prop::collection::vec(any::<bool>(), w..w * 4)))
+        ) {
+            let mut grid = BitGrid::new(width, cells.len() / width);
+            for (idx, cell) in cells.iter().enumerate().take(width * grid.height()) {
+                grid.set((idx % width, idx / width), *cell);
+            }
+            let counts = neighbour_counts(&grid);
+            prop_assert_eq!(&counts, &neighbour_counts_scalar(&grid));
+            for n in 0..=9 {
+                let few = counts.fewer_than(n);
+                let expected = (0..grid.height())
+                    .flat_map(|y| (0..width).map(move |x| (x, y)))
+                    .filter(|pos| grid.neighbour_count(*pos) < n);
+                prop_assert!(few.iter().eq(expected));
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Time budgets of the parts, declared with [`crate::parts!`] in the day's module, the
+//! list its binary runs (see [`crate::aoc!`]):
+//!
+//! ```ignore
+//! pub const PARTS: &[Part] = crate::parts!(part_one < 5 ms, part_two < 40 ms);
+//! ```
+//!
+//! Running a day or the season with `--budget` fails when a part goes over its
+//! budget on the real input. Budgets are scaled by `--budget-multiplier <x>` (or the
+//! `AOC_BUDGET_MULTIPLIER` env variable) on slower machines. Every checked run is
+//! appended to [`TREND_FILE`], so that timings can be compared with the previous run.
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// `unix_secs,day,part,nanos,budget_nanos` lines, in the working directory.
+pub const TREND_FILE: &str = ".aoc-trend.csv";
+
+/// A multiplier budgets can be scaled by: finite and more than 0.
+pub fn parse_multiplier(text: &str) -> Option<f64> {
+    text.parse()
+        .ok()
+        .filter(|m: &f64| m.is_finite() && *m > 0.0)
+}
+
+/// The `--budget-multiplier`, already parsed, or the env variable.
+pub fn multiplier(option: Option<f64>) -> f64 {
+    option
+        .or_else(|| {
+            std::env::var("AOC_BUDGET_MULTIPLIER").ok().map(|m| {
+                parse_multiplier(&m).expect("AOC_BUDGET_MULTIPLIER must be a positive number")
+            })
+        })
+        .unwrap_or(1.0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Check {
+    pub elapsed: Duration,
+    /// Already scaled by the multiplier
+    pub budget: Duration,
+    pub previous: Option<Duration>,
+}
+
+impl Check {
+    pub fn new(
+        day: &str,
+        part: &str,
+        elapsed: Duration,
+        budget: Duration,
+        multiplier: f64,
+    ) -> Self {
+        Check {
+            elapsed,
+            budget: budget.mul_f64(multiplier),
+            previous: previous(day, part),
+        }
+    }
+
+    pub fn exceeded(&self) -> bool {
+        self.elapsed > self.budget
+    }
+
+    pub fn describe(&self) -> String {
+        let used = self.elapsed.as_secs_f64() / self.budget.as_secs_f64() * 100.0;
+        let mut out = format!("{used:.1}% of {:?}", self.budget);
+        if let Some(previous) = self.previous {
+            let change = (self.elapsed.as_secs_f64() / previous.as_secs_f64() - 1.0) * 100.0;
+            out.push_str(&format!(", previous run {previous:.2?} ({change:+.0}%)"));
+        }
+        if self.exceeded() {
+            out.push_str(", OVER BUDGET");
+        }
+        out
+    }
+}
+
+/// Last recorded time of a part in the trend file.
+pub fn previous(day: &str, part: &str) -> Option<Duration> {
+    let trend = std::fs::read_to_string(TREND_FILE).ok()?;
+    trend.lines().rev().find_map(|line| {
+        let mut fields = line.split(',');
+        let (_, d, p, nanos) = (
+            fields.next()?,
+            fields.next()?,
+            fields.next()?,
+            fields.next()?,
+        );
+        (d == day && p == part)
+            .then(|| nanos.parse().ok().map(Duration::from_nanos))
+            .flatten()
+    })
+}
+
+pub fn record(day: &str, part: &str, check: &Check) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let line = format!(
+        "{now},{day},{part},{},{}\n",
+        check.elapsed.as_nanos(),
+        check.budget.as_nanos()
+    );
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(TREND_FILE)
+        .and_then(|mut file| file.write_all(line.as_bytes()));
+    if let Err(err) = written {
+        eprintln!("Failed to update {TREND_FILE}: {err}");
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_check() {
+        let check = Check {
+            elapsed: Duration::from_millis(30),
+            budget: Duration::from_millis(20).mul_f64(2.0),
+            previous: Some(Duration::from_millis(20)),
+        };
+        assert!(!check.exceeded());
+        assert_eq!(
+            check.describe(),
+            "75.0% of 40ms, previous run 20.00ms (+50%)"
+        );
+
+        let check = Check {
+            previous: None,
+            budget: Duration::from_millis(20),
+            ..check
+        };
+        assert!(check.exceeded());
+        assert_eq!(check.describe(), "150.0% of 20ms, OVER BUDGET");
+    }
+
+    #[test]
+    fn budget_multiplier() {
+        assert_eq!(parse_multiplier("2.5"), Some(2.5));
+        for invalid in ["-1", "0", "NaN", "inf", "x"] {
+            assert_eq!(parse_multiplier(invalid), None, "{invalid}");
+        }
+        assert_eq!(multiplier(Some(3.0)), 3.0);
+    }
+}
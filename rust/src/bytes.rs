@@ -0,0 +1,137 @@
+//! Parsing straight from the input bytes, without UTF-8 checks nor temporary strings.
+//!
+//! Parts can take their input as `&[u8]` instead of `&str`, see
+//! [`FromInput`](crate::runner::FromInput).
+
+/// Lines of `input`, ended by `\n` or `\r\n`. Like [`str::lines`], a last empty line
+/// isn't one.
+pub fn lines(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let empty = input.is_empty();
+    let input = input.strip_suffix(b"\n").unwrap_or(input);
+    input
+        .split(|b| *b == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .filter(move |_| !empty)
+}
+
+/// The value of every digit, or the byte that isn't one.
+pub fn digits(bytes: &[u8]) -> impl Iterator<Item = Result<u8, u8>> + '_ {
+    bytes.iter().map(|b| match b {
+        b'0'..=b'9' => Ok(b - b'0'),
+        b => Err(*b),
+    })
+}
+
+/// Integers [`uint`] and [`int`] can read.
+pub trait Int: Copy {
+    const ZERO: Self;
+    const SIGNED: bool;
+    fn push_digit(self, digit: u8) -> Option<Self>;
+    /// Same as `push_digit`, for a negative number
+    fn push_negative_digit(self, digit: u8) -> Option<Self>;
+}
+
+macro_rules! int {
+    ($signed:literal => $($int:ty),*) => {
+        $(
+            impl Int for $int {
+                const ZERO: Self = 0;
+                const SIGNED: bool = $signed;
+
+                fn push_digit(self, digit: u8) -> Option<Self> {
+                    self.checked_mul(10)?.checked_add(digit as Self)
+                }
+
+                fn push_negative_digit(self, digit: u8) -> Option<Self> {
+                    self.checked_mul(10)?.checked_sub(digit as Self)
+                }
+            }
+        )*
+    };
+}
+
+int!(false => u8, u16, u32, u64, u128, usize);
+int!(true => i8, i16, i32, i64, i128, isize);
+
+fn digits_to<T: Int>(bytes: &[u8], negative: bool) -> Option<T> {
+    if bytes.is_empty() {
+        return None;
+    }
+    bytes.iter().try_fold(T::ZERO, |n, b| match b {
+        b'0'..=b'9' if negative => n.push_negative_digit(b - b'0'),
+        b'0'..=b'9' => n.push_digit(b - b'0'),
+        _ => None,
+    })
+}
+
+/// The number written by all of `bytes`, `None` if it isn't one or overflows.
+pub fn uint<T: Int>(bytes: &[u8]) -> Option<T> {
+    digits_to(bytes, false)
+}
+
+/// Same as [`uint`], with an optional sign.
+pub fn int<T: Int>(bytes: &[u8]) -> Option<T> {
+    match bytes {
+        [b'-', rest @ ..] if T::SIGNED => digits_to(rest, true),
+        [b'+', rest @ ..] => uint(rest),
+        _ => uint(bytes),
+    }
+}
+
+/// Every number in `bytes`, whatever separates them. A `-` right before a number
+/// makes it negative. `None` for the numbers that overflow.
+pub fn ints<T: Int>(bytes: &[u8]) -> impl Iterator<Item = Option<T>> + '_ {
+    let mut rest = bytes;
+    std::iter::from_fn(move || {
+        let start = rest.iter().position(u8::is_ascii_digit)?;
+        let len = rest[start..]
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .unwrap_or(rest.len() - start);
+        let negative = T::SIGNED && start > 0 && rest[start - 1] == b'-';
+        let number = digits_to(&rest[start..start + len], negative);
+        rest = &rest[start + len..];
+        Some(number)
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_lines() {
+        fn split(input: &str) -> Vec<&[u8]> {
+            lines(input.as_bytes()).collect()
+        }
+        assert_eq!(split("a\r\nb\n\nc\n"), [&b"a"[..], b"b", b"", b"c"]);
+        assert_eq!(split("a\nb"), [&b"a"[..], b"b"]);
+        assert!(split("").is_empty());
+        for input in ["1\r\n2\n\n3\r\n", "x\n\n", "\n"] {
+            let expected: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+            assert_eq!(split(input), expected);
+        }
+    }
+
+    #[test]
+    fn bytes_numbers() {
+        assert_eq!(uint::<u8>(b"255"), Some(255));
+        assert_eq!(uint::<u8>(b"256"), None);
+        assert_eq!(uint::<u32>(b""), None);
+        assert_eq!(uint::<u32>(b"-1"), None);
+        assert_eq!(int::<i64>(b"-42"), Some(-42));
+        assert_eq!(int::<i64>(b"+42"), Some(42));
+        assert_eq!(int::<i8>(b"-128"), Some(-128));
+        assert_eq!(int::<i8>(b"-129"), None);
+        assert_eq!(int::<u64>(b"-42"), None);
+        assert_eq!(int::<i64>(b"4-2"), None);
+
+        let scanned: Vec<_> = ints::<i32>(b"x=-3, y=14..-0 z 99999999999").collect();
+        assert_eq!(scanned, [Some(-3), Some(14), Some(0), None]);
+        let scanned: Vec<_> = ints::<u32>(b"L-3,R7").collect();
+        assert_eq!(scanned, [Some(3), Some(7)]);
+
+        let parsed: Vec<_> = digits(b"10x").collect();
+        assert_eq!(parsed, [Ok(1), Ok(0), Err(b'x')]);
+    }
+}
@@ -0,0 +1,384 @@
+//! Registry of the memoized (`#[cached]`) functions, so the runner can reset them
+//! between parts and report how they did.
+//!
+//! A memoized function uses [`Store`] as its cache:
+//!
+//! ```ignore
+//! #[cached(ty = "Store<(i32, i32), u64>", create = "{ Store::new() }")]
+//! fn around(x: i32, y: i32) -> u64 { ... }
+//!
+//! pub static CACHES: [Entry; 1] = [Entry::new("dayN::around", &AROUND)];
+//! ```
+//!
+//! and the day's `CACHES` are listed in [`crate::days::caches`].
+//!
+//! A recursive solution over borrowed input rather uses a [`Scoped`] cache, keyed by
+//! something compact (indices, bitsets) instead of the state itself, living as long
+//! as the call that creates it:
+//!
+//! ```ignore
+//! fn count(memo: &mut Scoped<[Row], (usize, usize), u64>, (row, x): (usize, usize)) -> u64 {
+//!     let rows = memo.context();
+//!     ... memo.get((row + 1, x)) ...
+//! }
+//!
+//! Scoped::new("day7::count", &rows, count).get((0, start))
+//! ```
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use cached::{once_cell::sync::Lazy, Cached, SizedCache, UnboundCache};
+
+/// 0 for unbound caches
+static CAPACITY: AtomicUsize = AtomicUsize::new(0);
+
+/// Caps the registered caches created from now on, or the ones reset by [`reset_all`].
+///
+/// [`Scoped`] caches are never capped: evicting what a recursion still needs makes
+/// it exponential.
+pub fn set_capacity(capacity: Option<usize>) {
+    CAPACITY.store(capacity.unwrap_or(0), Ordering::Relaxed);
+}
+
+pub fn capacity() -> Option<usize> {
+    match CAPACITY.load(Ordering::Relaxed) {
+        0 => None,
+        capacity => Some(capacity),
+    }
+}
+
+/// Unbound cache, or a LRU one when a [`capacity`] is set.
+pub enum Store<K, V> {
+    Unbound(UnboundCache<K, V>),
+    Sized(SizedCache<K, V>),
+}
+
+impl<K: Hash + Eq + Clone, V> Store<K, V> {
+    pub fn new() -> Self {
+        match capacity() {
+            None => Store::Unbound(UnboundCache::new()),
+            Some(capacity) => Store::Sized(SizedCache::with_size(capacity)),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Default for Store<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+macro_rules! delegate {
+    ($self:ident, $cache:ident => $call:expr) => {
+        match $self {
+            Store::Unbound($cache) => $call,
+            Store::Sized($cache) => $call,
+        }
+    };
+}
+
+impl<K: Hash + Eq + Clone, V> Cached<K, V> for Store<K, V> {
+    fn cache_get<Q>(&mut self, k: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        delegate!(self, c => c.cache_get(k))
+    }
+
+    fn cache_get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        delegate!(self, c => c.cache_get_mut(k))
+    }
+
+    fn cache_set(&mut self, k: K, v: V) -> Option<V> {
+        delegate!(self, c => c.cache_set(k, v))
+    }
+
+    fn cache_get_or_set_with<F: FnOnce() -> V>(&mut self, k: K, f: F) -> &mut V {
+        delegate!(self, c => c.cache_get_or_set_with(k, f))
+    }
+
+    fn cache_try_get_or_set_with<F: FnOnce() -> Result<V, E>, E>(
+        &mut self,
+        k: K,
+        f: F,
+    ) -> Result<&mut V, E> {
+        delegate!(self, c => c.cache_try_get_or_set_with(k, f))
+    }
+
+    fn cache_remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        delegate!(self, c => c.cache_remove(k))
+    }
+
+    fn cache_clear(&mut self) {
+        delegate!(self, c => c.cache_clear())
+    }
+
+    fn cache_reset(&mut self) {
+        delegate!(self, c => c.cache_reset())
+    }
+
+    fn cache_reset_metrics(&mut self) {
+        delegate!(self, c => c.cache_reset_metrics())
+    }
+
+    fn cache_size(&self) -> usize {
+        delegate!(self, c => c.cache_size())
+    }
+
+    fn cache_hits(&self) -> Option<u64> {
+        delegate!(self, c => c.cache_hits())
+    }
+
+    fn cache_misses(&self) -> Option<u64> {
+        delegate!(self, c => c.cache_misses())
+    }
+
+    fn cache_capacity(&self) -> Option<usize> {
+        delegate!(self, c => c.cache_capacity())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub capacity: Option<usize>,
+    /// Shallow: what keys and values own on the heap isn't counted.
+    pub bytes: usize,
+}
+
+impl Stats {
+    fn add(&mut self, other: &Stats) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.entries += other.entries;
+        self.capacity = self.capacity.or(other.capacity);
+        self.bytes += other.bytes;
+    }
+
+    pub fn hit_ratio(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}%), {} entries",
+            self.hits,
+            self.misses,
+            self.hit_ratio() * 100.0,
+            self.entries,
+        )?;
+        if let Some(capacity) = self.capacity {
+            write!(f, " (cap {capacity})")?;
+        }
+        write!(f, ", ~{} KiB", self.bytes.div_ceil(1024))
+    }
+}
+
+/// What the registry needs from a cache static.
+pub trait Memo: Sync {
+    /// Empties the cache and its metrics, and applies the current [`capacity`].
+    fn reset(&self);
+    fn stats(&self) -> Stats;
+}
+
+impl<K, V> Memo for Lazy<Mutex<Store<K, V>>>
+where
+    K: Hash + Eq + Clone + Send,
+    V: Send,
+{
+    fn reset(&self) {
+        *self.lock().expect("cache lock") = Store::new();
+    }
+
+    fn stats(&self) -> Stats {
+        store_stats(&self.lock().expect("cache lock"))
+    }
+}
+
+fn store_stats<K: Hash + Eq + Clone, V>(store: &Store<K, V>) -> Stats {
+    let entries = store.cache_size();
+    Stats {
+        hits: store.cache_hits().unwrap_or(0),
+        misses: store.cache_misses().unwrap_or(0),
+        entries,
+        capacity: store.cache_capacity(),
+        bytes: entries * (size_of::<K>() + size_of::<V>()),
+    }
+}
+
+pub struct Entry {
+    pub name: &'static str,
+    memo: &'static dyn Memo,
+}
+
+impl Entry {
+    pub const fn new(name: &'static str, memo: &'static dyn Memo) -> Self {
+        Entry { name, memo }
+    }
+
+    pub fn reset(&self) {
+        self.memo.reset()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.memo.stats()
+    }
+}
+
+/// Memoization of `f` for the duration of a call, see the [module](self) docs.
+///
+/// `f` gets the cache back to recurse through [`Scoped::get`], and finds what it works
+/// on in [`Scoped::context`], so keys only need to tell the calls apart. What the
+/// caches of a name did is added up when they're dropped, and reported by [`used`].
+pub struct Scoped<'c, C: ?Sized, K: Hash + Eq + Clone, V> {
+    name: &'static str,
+    context: &'c C,
+    store: Store<K, V>,
+    f: fn(&mut Self, K) -> V,
+}
+
+/// Stats of the dropped [`Scoped`] caches, by name.
+static SCOPED: Mutex<Option<HashMap<&'static str, Stats>>> = Mutex::new(None);
+
+impl<C: ?Sized, K: Hash + Eq + Clone, V> Scoped<'_, C, K, V> {
+    pub fn stats(&self) -> Stats {
+        store_stats(&self.store)
+    }
+}
+
+impl<'c, C: ?Sized, K: Hash + Eq + Clone, V: Clone> Scoped<'c, C, K, V> {
+    pub fn new(name: &'static str, context: &'c C, f: fn(&mut Self, K) -> V) -> Self {
+        Scoped {
+            name,
+            context,
+            store: Store::Unbound(UnboundCache::new()),
+            f,
+        }
+    }
+
+    pub fn context(&self) -> &'c C {
+        self.context
+    }
+
+    /// `f(key)`, computed once.
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.store.cache_get(&key) {
+            return value.clone();
+        }
+        let value = (self.f)(self, key.clone());
+        self.store.cache_set(key, value.clone());
+        value
+    }
+}
+
+impl<C: ?Sized, K: Hash + Eq + Clone, V> Drop for Scoped<'_, C, K, V> {
+    fn drop(&mut self) {
+        let stats = self.stats();
+        let mut scoped = SCOPED.lock().expect("scoped stats lock");
+        let totals = scoped.get_or_insert_with(HashMap::new);
+        totals.entry(self.name).or_default().add(&stats);
+    }
+}
+
+pub fn reset_all() {
+    crate::days::caches().for_each(Entry::reset);
+    *SCOPED.lock().expect("scoped stats lock") = None;
+}
+
+/// Resets the caches named `<day>::...`
+pub fn reset_day(day: &str) {
+    let of_day = |name: &str| name.split("::").next() == Some(day);
+    crate::days::caches()
+        .filter(|entry| of_day(entry.name))
+        .for_each(Entry::reset);
+    if let Some(totals) = SCOPED.lock().expect("scoped stats lock").as_mut() {
+        totals.retain(|name, _| !of_day(name));
+    }
+}
+
+/// Stats of the caches that were used since they were last reset.
+pub fn used() -> Vec<(&'static str, Stats)> {
+    let scoped = SCOPED.lock().expect("scoped stats lock");
+    let mut scoped: Vec<_> = scoped.iter().flatten().map(|(n, s)| (*n, *s)).collect();
+    scoped.sort_by_key(|(name, _)| *name);
+    crate::days::caches()
+        .map(|entry| (entry.name, entry.stats()))
+        .chain(scoped)
+        .filter(|(_, stats)| stats.hits + stats.misses > 0)
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    static SQUARES: Lazy<Mutex<Store<u64, u64>>> = Lazy::new(|| Mutex::new(Store::new()));
+
+    fn square(x: u64) -> u64 {
+        *SQUARES.lock().unwrap().cache_get_or_set_with(x, || x * x)
+    }
+
+    #[test]
+    fn cache_stats() {
+        let entry = Entry::new("squares", &SQUARES);
+        for x in [1, 2, 1, 3, 1] {
+            square(x);
+        }
+        let stats = entry.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (2, 3, 3));
+        assert_eq!(stats.capacity, None);
+
+        entry.reset();
+        assert_eq!(entry.stats(), Stats::default());
+    }
+
+    fn fibonacci(memo: &mut Scoped<[u64], usize, u64>, n: usize) -> u64 {
+        match n {
+            0 | 1 => memo.context()[n],
+            n => memo.get(n - 1) + memo.get(n - 2),
+        }
+    }
+
+    #[test]
+    fn cache_scoped() {
+        let mut memo = Scoped::new("test::fibonacci", &[0, 1][..], fibonacci);
+        assert_eq!(memo.get(90), 2880067194370816120);
+        let stats = memo.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (88, 91, 91));
+    }
+
+    #[test]
+    fn cache_store_capacity() {
+        let mut store = Store::Sized(SizedCache::with_size(2));
+        for x in 0..10u64 {
+            store.cache_set(x, x);
+        }
+        assert_eq!(store.cache_size(), 2);
+        assert_eq!(store.cache_capacity(), Some(2));
+    }
+}
@@ -0,0 +1,84 @@
+//! Checks a fast solution against a slow reference one on random inputs.
+//!
+//! A day provides a [`Strategy`] generating valid inputs, and a reference solver that
+//! is obviously right even if it's slow:
+//!
+//! ```ignore
+//! check::against_reference(small_inputs(), part_two, brute_force);
+//! ```
+use std::fmt::Debug;
+
+pub use proptest;
+use proptest::{
+    prop_assert_eq,
+    strategy::Strategy,
+    test_runner::{Config, TestRunner},
+};
+
+/// Overridden by the `PROPTEST_CASES` env variable.
+pub const CASES: u32 = 2000;
+
+pub fn cases() -> u32 {
+    std::env::var("PROPTEST_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(CASES)
+}
+
+/// Panics with the smallest failing input found if `fast` and `reference` disagree.
+pub fn against_reference<S, T, F, R>(inputs: S, fast: F, reference: R)
+where
+    S: Strategy<Value = String>,
+    T: PartialEq + Debug,
+    F: Fn(&str) -> T,
+    R: Fn(&str) -> T,
+{
+    let config = Config {
+        cases: cases(),
+        failure_persistence: None,
+        ..Config::default()
+    };
+    let mut runner = TestRunner::new(config);
+    let result = runner.run(&inputs, |input| {
+        prop_assert_eq!(fast(&input), reference(&input), "input:\n{}", input);
+        Ok(())
+    });
+
+    if let Err(err) = result {
+        panic!("{err}");
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn numbers() -> impl Strategy<Value = String> {
+        prop::collection::vec(0u32..100, 0..20).prop_map(|v| {
+            v.iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
+
+    fn sum(input: &str) -> u32 {
+        input.lines().map(|l| l.parse::<u32>().unwrap()).sum()
+    }
+
+    #[test]
+    fn check_agrees() {
+        against_reference(numbers(), sum, |input| {
+            input
+                .lines()
+                .fold(0, |acc, l| acc + l.parse::<u32>().unwrap())
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "input:")]
+    fn check_disagrees() {
+        against_reference(numbers(), sum, |input| input.lines().count() as u32);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{cache::Entry, runner::Day};
+
+pub mod day1;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+
+pub static ALL: &[Day] = &[
+    Day {
+        name: "day1",
+        parts: day1::PARTS,
+    },
+    Day {
+        name: "day3",
+        parts: day3::PARTS,
+    },
+    Day {
+        name: "day4",
+        parts: day4::PARTS,
+    },
+    Day {
+        name: "day5",
+        parts: day5::PARTS,
+    },
+    Day {
+        name: "day6",
+        parts: day6::PARTS,
+    },
+    Day {
+        name: "day7",
+        parts: day7::PARTS,
+    },
+    Day {
+        name: "day8",
+        parts: day8::PARTS,
+    },
+];
+
+/// Every memoized function, see [`crate::cache`]. The days' recursive solutions all
+/// memoize with [`Scoped`](crate::cache::Scoped) caches for now, so there are none.
+pub fn caches() -> impl Iterator<Item = &'static Entry> {
+    std::iter::empty()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{input::Input, Answer};
+    use aoc25_generate::{Rng, GENERATORS};
+
+    #[test]
+    fn days_generated_inputs() {
+        for seed in 0..10 {
+            for (day, _, generator) in GENERATORS {
+                let mut input = Vec::new();
+                let known = generator(30, &mut Rng::new(seed), &mut input).expect("a Vec");
+                let parts = ALL.iter().find(|d| d.name == *day).unwrap().parts;
+                for part in parts {
+                    let answer = (part.run)(&Input::new(&input));
+                    if let Some((_, expected)) = known.iter().find(|(p, _)| *p == part.name) {
+                        let expected = Answer::from(*expected);
+                        assert_eq!(answer, expected, "{day} {} seed {seed}", part.name);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    bytes,
+    dial::Dial,
+    input::{self, LineSource},
+    parse::ParseError,
+    runner::Part,
+};
+
+fn rotation(idx: usize, line: &[u8]) -> Result<i64, ParseError> {
+    let (ident, rest) = line
+        .split_first()
+        .filter(|(ident, _)| ident.is_ascii())
+        .ok_or_else(|| ParseError::at(idx, "expected a direction"))?;
+    let value: u32 = bytes::int(rest).ok_or_else(|| ParseError::at(idx, "wrong format"))?;
+    match ident {
+        b'L' => Ok(-(value as i64)),
+        b'R' => Ok(value as i64),
+        x => Err(ParseError::at(
+            idx,
+            format!("unexpected ident {}", *x as char),
+        )),
+    }
+}
+
+pub fn parse(input: &[u8]) -> Result<Vec<i64>, ParseError> {
+    bytes::lines(input)
+        .enumerate()
+        .map(|(idx, l)| rotation(idx, l))
+        .collect()
+}
+
+/// Calls `f` with every rotation, in one pass.
+fn rotations(lines: &mut dyn LineSource, mut f: impl FnMut(i64)) {
+    let mut idx = 0;
+    while let Some(line) = lines.next_line() {
+        f(rotation(idx, line).expect("valid input"));
+        idx += 1;
+    }
+}
+
+pub fn part_one(input: &[u8]) -> i64 {
+    stream_one(&mut input::lines(input))
+}
+
+pub fn stream_one(lines: &mut dyn LineSource) -> i64 {
+    let mut password = 0;
+    let mut dial = Dial::new(100, 50);
+
+    rotations(lines, |i| {
+        dial.rotate(i);
+        if dial.position() == 0 {
+            password += 1;
+        }
+    });
+
+    password
+}
+
+pub fn part_two(input: &[u8]) -> i64 {
+    stream_two(&mut input::lines(input))
+}
+
+pub fn stream_two(lines: &mut dyn LineSource) -> i64 {
+    let mut password = 0;
+    let mut dial = Dial::new(100, 50);
+
+    rotations(lines, |i| {
+        password += dial.passes(i, 0);
+        dial.rotate(i);
+    });
+
+    password
+}
+
+pub const PARTS: &[Part] = crate::parts!(
+    part_one streaming stream_one < 5 ms,
+    part_two streaming stream_two < 5 ms
+);
+
+crate::examples!(day1);
+
+#[cfg(test)]
+pub mod tests {
+    use crate::check::{self, proptest::prelude::*};
+
+    const TEST_INPUT: &str = "L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82";
+
+    #[test]
+    fn day1() {
+        assert_eq!(super::part_one(TEST_INPUT.as_bytes()), 3);
+        assert_eq!(super::part_two(TEST_INPUT.as_bytes()), 6);
+    }
+
+    #[test]
+    fn day1_stream() {
+        use crate::input::Stream;
+        let windows = TEST_INPUT.replace('\n', "\r\n");
+        assert_eq!(super::stream_one(&mut Stream::new(windows.as_bytes())), 3);
+        assert_eq!(super::stream_two(&mut Stream::new(windows.as_bytes())), 6);
+        assert_eq!(
+            super::parse(windows.as_bytes()),
+            super::parse(TEST_INPUT.as_bytes()),
+            "CRLF lines"
+        );
+    }
+
+    fn rotations() -> impl Strategy<Value = String> {
+        let rotation = (prop::bool::ANY, 1i64..500)
+            .prop_map(|(left, value)| format!("{}{value}", if left { 'L' } else { 'R' }));
+        prop::collection::vec(rotation, 1..40).prop_map(|lines| lines.join("\n"))
+    }
+
+    fn click_by_click(input: &str) -> i64 {
+        let mut password = 0;
+        let mut dial = 50;
+        for i in super::parse(input.as_bytes()).expect("valid input") {
+            for _ in 0..i.abs() {
+                dial = (dial + i.signum()).rem_euclid(100);
+                if dial == 0 {
+                    password += 1;
+                }
+            }
+        }
+        password
+    }
+
+    #[test]
+    fn day1_against_reference() {
+        check::against_reference(
+            rotations(),
+            |input| super::part_two(input.as_bytes()),
+            click_by_click,
+        );
+    }
+
+    #[test]
+    fn day1_fuzz_parse() {
+        crate::fuzz::parser(TEST_INPUT, |input| super::parse(input.as_bytes()));
+    }
+}
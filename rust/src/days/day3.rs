@@ -0,0 +1,164 @@
+use crate::{bytes, cache::Scoped, input::LineSource, par, parse::ParseError, runner::Part};
+
+fn bank(idx: usize, line: &[u8]) -> Result<Vec<u64>, ParseError> {
+    bytes::digits(line)
+        .map(|d| match d {
+            Ok(d) => Ok(d as u64),
+            Err(b) => Err(ParseError::at(idx, format!("unexpected byte {b:#04x}"))),
+        })
+        .collect()
+}
+
+pub fn parse(input: &[u8]) -> Result<Vec<Vec<u64>>, ParseError> {
+    bytes::lines(input)
+        .enumerate()
+        .map(|(idx, line)| bank(idx, line))
+        .collect()
+}
+
+/// Sum of the joltages of every bank, in one pass.
+fn stream(lines: &mut dyn LineSource, leftover: u32) -> u128 {
+    let mut total = 0;
+    let mut idx = 0;
+    while let Some(line) = lines.next_line() {
+        let bank = bank(idx, line).expect("valid input");
+        total += joltage(&bank, leftover) as u128;
+        idx += 1;
+    }
+    total
+}
+
+pub fn part_one(input: &[u8]) -> u64 {
+    let lines = parse(input).expect("valid input");
+    par::map(&lines, |line| joltage(line, 1)).into_iter().sum()
+}
+
+// Joltages are below 10^12, far from overflowing a u128 sum whatever the input size
+pub fn part_two(input: &[u8]) -> u128 {
+    let lines = parse(input).expect("valid input");
+    par::map(&lines, |line| joltage(line, 11) as u128)
+        .into_iter()
+        .sum()
+}
+
+pub fn stream_one(lines: &mut dyn LineSource) -> u128 {
+    stream(lines, 1)
+}
+
+pub fn stream_two(lines: &mut dyn LineSource) -> u128 {
+    stream(lines, 11)
+}
+
+fn joltage(digits: &[u64], leftover: u32) -> u64 {
+    Scoped::new("day3::joltage", digits, joltage_from).get((0, leftover))
+}
+
+// Best joltage from the digits at `start` on, keeping `leftover + 1` of them
+fn joltage_from(
+    memo: &mut Scoped<[u64], (usize, u32), u64>,
+    (start, leftover): (usize, u32),
+) -> u64 {
+    let input = &memo.context()[start..];
+    if input.len() <= leftover as usize {
+        return input.iter().fold(0, |acc, x| x + acc * 10);
+    }
+
+    let next = start + 1;
+    match leftover {
+        0 => u64::max(input[0], memo.get((next, 0))),
+        _ => {
+            let recursed = memo.get((next, leftover));
+            let current = input[0] * u64::pow(10, leftover) + memo.get((next, leftover - 1));
+            u64::max(recursed, current)
+        }
+    }
+}
+
+pub const PARTS: &[Part] = crate::parts!(
+    part_one streaming stream_one < 50 ms,
+    part_two streaming stream_two < 150 ms
+);
+
+crate::examples!(day3);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::check::{self, proptest::prelude::*};
+
+    const INPUT: &str = "987654321111111
+811111111111119
+234234234234278
+818181911112111";
+
+    #[test]
+    fn day3() {
+        assert_eq!(part_one(INPUT.as_bytes()), 357);
+        assert_eq!(part_two(INPUT.as_bytes()), 3121910778619);
+    }
+
+    #[test]
+    fn day3_stream() {
+        let lines = prop::collection::vec("[1-9]{12,16}", 1..8).prop_map(|l| l.join("\n"));
+        check::against_reference(
+            lines,
+            |input| stream_two(&mut crate::input::Stream::new(input.as_bytes())),
+            |input| part_two(input.as_bytes()),
+        );
+        assert_eq!(stream_one(&mut crate::input::lines(INPUT.as_bytes())), 357);
+    }
+
+    #[test]
+    fn day3_parallel() {
+        let lines = prop::collection::vec("[1-9]{12,16}", 1..8).prop_map(|l| l.join("\n"));
+        check::against_reference(
+            lines,
+            |input| part_two(input.as_bytes()),
+            |input| par::sequential(|| part_two(input.as_bytes())),
+        );
+    }
+
+    #[test]
+    fn day3_fuzz_parse() {
+        crate::fuzz::parser(INPUT, |input| parse(input.as_bytes()));
+    }
+
+    #[test]
+    fn day3_joltage() {
+        assert_eq!(
+            joltage(&[9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1], 1),
+            98,
+        );
+        assert_eq!(
+            joltage(&[9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1], 11),
+            987654321111
+        );
+
+        assert_eq!(
+            joltage(&[8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 9], 1),
+            89
+        );
+        assert_eq!(
+            joltage(&[8, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 9], 11),
+            811111111119
+        );
+
+        assert_eq!(
+            joltage(&[2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8], 1),
+            78
+        );
+        assert_eq!(
+            joltage(&[2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 3, 4, 2, 7, 8], 11),
+            434234234278
+        );
+
+        assert_eq!(
+            joltage(&[8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1], 1),
+            92
+        );
+        assert_eq!(
+            joltage(&[8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1], 11),
+            888911112111
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    automaton::Automaton,
+    bits::{kernel, BitGrid},
+    grid::{Grid, MOORE},
+    parse::ParseError,
+    runner::Part,
+    visualize::{self, Frame},
+};
+
+pub fn parse_grid(input: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::try_parse(input, |c| match c {
+        '@' => Ok(true),
+        '.' => Ok(false),
+        c => Err(format!("Unexpected char {c}")),
+    })
+}
+
+/// The rolls with fewer than 4 neighbours, word by word, see [`kernel`].
+pub fn accessible(rolls: &BitGrid) -> BitGrid {
+    let mut accessible = kernel::neighbour_counts(rolls).fewer_than(4);
+    accessible.intersection_with(rolls);
+    accessible
+}
+
+pub fn part_one(input: &str) -> usize {
+    let rolls = BitGrid::from(&parse_grid(input).expect("valid input"));
+    accessible(&rolls).count()
+}
+
+// Only the neighbours of removed rolls get looked at again
+pub fn part_two(input: &str) -> usize {
+    let grid = parse_grid(input).expect("valid input");
+
+    let mut generation = 0;
+    let mut removed_at = visualize::recording().then(|| grid.map(|_| None));
+    let run = Automaton::new(&MOORE, usize::MAX).run_inspect(
+        grid,
+        |roll, neighbours| (*roll && neighbours.filter(|n| **n).count() < 4).then_some(false),
+        |grid, removed| {
+            generation += 1;
+            let Some(removed_at) = &mut removed_at else {
+                return;
+            };
+            for pos in removed {
+                removed_at[*pos] = Some(generation);
+            }
+            visualize::emit(|| {
+                let caption = format!("round {generation}, removed {}", removed.len());
+                let picture = grid.map(|roll| if *roll { '@' } else { '.' });
+                let mut frame = Frame::new(caption, picture).highlight(removed.iter().copied());
+                for pos in removed {
+                    frame.grid[*pos] = 'x';
+                }
+                // Removed rolls fade out over a few rounds
+                frame.heat(removed_at.map(|at| match at {
+                    Some(at) => 1.0 - (generation - at) as f32 / 8.0,
+                    None => 0.0,
+                }))
+            });
+        },
+    );
+    run.generations.iter().sum()
+}
+
+pub const PARTS: &[Part] = crate::parts!(part_one < 50 ms, part_two < 50 ms);
+
+crate::examples!(day4);
+
+#[cfg(test)]
+pub mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::check::{self, proptest::prelude::*};
+
+    // The hash set solution the bit grid replaced, as a reference
+    struct Map {
+        data: HashSet<(i32, i32)>,
+    }
+
+    fn parse(input: &str) -> Result<Map, ParseError> {
+        let data = input
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars().enumerate().filter_map(move |(x, c)| match c {
+                    '@' => Some(Ok((x as i32, y as i32))),
+                    '.' => None,
+                    c => Some(Err(ParseError::at(y, format!("Unexpected char {c}")))),
+                })
+            })
+            .collect::<Result<HashSet<(i32, i32)>, ParseError>>()?;
+        Ok(Map { data })
+    }
+
+    fn paper_rolls(data: &HashSet<(i32, i32)>) -> HashSet<(i32, i32)> {
+        data.iter()
+            .filter(|(x, y)| {
+                let around = (y - 1..=y + 1).flat_map(|yy| (x - 1..=x + 1).map(move |xx| (xx, yy)));
+                around
+                    .filter(|pos| *pos != (*x, *y) && data.contains(pos))
+                    .count()
+                    < 4
+            })
+            .cloned()
+            .collect()
+    }
+
+    const INPUT: &str = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+
+    #[test]
+    fn day4() {
+        assert_eq!(part_one(INPUT), 13);
+        assert_eq!(part_two(INPUT), 43);
+    }
+
+    fn with_paper_rolls(input: &str) -> (usize, usize) {
+        let Map { mut data } = parse(input).unwrap();
+        let part_one = paper_rolls(&data).len();
+        let mut part_two = 0;
+        loop {
+            let removed = paper_rolls(&data);
+            if removed.is_empty() {
+                return (part_one, part_two);
+            }
+            part_two += removed.len();
+            data.retain(|roll| !removed.contains(roll));
+        }
+    }
+
+    #[test]
+    fn day4_bits() {
+        assert_eq!(with_paper_rolls(INPUT), (part_one(INPUT), part_two(INPUT)));
+    }
+
+    #[test]
+    fn day4_against_reference() {
+        // Wide enough to span a few words
+        let grids = (1usize..140, 1usize..5).prop_flat_map(|(width, height)| {
+            let row = prop::collection::vec(prop_oneof![Just('.'), Just('@')], width);
+            prop::collection::vec(row.prop_map(String::from_iter), height)
+                .prop_map(|rows| rows.join("\n"))
+        });
+        check::against_reference(
+            grids,
+            |input| (part_one(input), part_two(input)),
+            with_paper_rolls,
+        );
+    }
+
+    #[test]
+    fn day4_fuzz_parse() {
+        crate::fuzz::parser(INPUT, parse);
+        crate::fuzz::parser(INPUT, parse_grid);
+    }
+}
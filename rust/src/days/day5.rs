@@ -0,0 +1,146 @@
+use std::ops::RangeInclusive;
+
+use crate::{parse::ParseError, runner::Part};
+
+pub struct Input {
+    ranges: Vec<RangeInclusive<u64>>,
+    ingredients: Vec<u64>,
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let mut splitted = input.split("\n\n");
+    let ranges = splitted
+        .next()
+        .ok_or_else(|| ParseError::new(0, "two parts"))?;
+    let ranges_len = ranges.lines().count();
+    let ranges = ranges
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            let number = |x: Option<&str>| {
+                x.and_then(|x| x.parse::<u64>().ok())
+                    .ok_or_else(|| ParseError::at(idx, "two valid numbers"))
+            };
+            let mut line = line.split("-");
+            let a = number(line.next())?;
+            let b = number(line.next())?;
+            if line.next().is_some() {
+                return Err(ParseError::at(idx, "two valid numbers"));
+            }
+            // part two counts up to end + 1
+            if a > b || b == u64::MAX {
+                return Err(ParseError::at(idx, format!("invalid range {a}-{b}")));
+            }
+            Ok(a..=b)
+        })
+        .collect::<Result<_, _>>()?;
+
+    let ingredients = splitted
+        .next()
+        .ok_or_else(|| ParseError::new(0, "two parts"))?;
+    let ingredients = ingredients
+        .lines()
+        .enumerate()
+        .map(|(idx, l)| {
+            l.parse()
+                .map_err(|_| ParseError::at(ranges_len + 1 + idx, "valid number"))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Input {
+        ranges,
+        ingredients,
+    })
+}
+
+pub fn part_one(input: &str) -> usize {
+    let Input {
+        ranges,
+        ingredients,
+    } = parse(input).expect("valid input");
+    ingredients
+        .iter()
+        .filter(|id| ranges.iter().any(|range| range.contains(id)))
+        .count()
+}
+
+pub fn part_two(input: &str) -> u64 {
+    let Input {
+        mut ranges,
+        ingredients: _,
+    } = parse(input).expect("valid input");
+
+    ranges.sort_by_key(|r| *r.start());
+    let mut idx = 0;
+    ranges.iter().fold(0, |mut acc, range| {
+        // Skip the range if already accounted for
+        if idx > *range.end() {
+            return acc;
+        }
+
+        if range.contains(&idx) {
+            acc += *range.end() - idx + 1;
+        } else {
+            acc += *range.end() - *range.start() + 1;
+        }
+
+        idx = *range.end() + 1;
+        acc
+    })
+}
+
+pub const PARTS: &[Part] = crate::parts!(part_one < 5 ms, part_two < 5 ms);
+
+crate::examples!(day5);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::check::{self, proptest::prelude::*};
+
+    const INPUT: &str = "3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32";
+
+    #[test]
+    fn day5() {
+        assert_eq!(part_one(INPUT), 3);
+        assert_eq!(part_two(INPUT), 14);
+    }
+
+    fn small_inputs() -> impl Strategy<Value = String> {
+        let range = (0u64..200, 0u64..30).prop_map(|(a, len)| format!("{a}-{}", a + len));
+        let ranges = prop::collection::vec(range, 1..20);
+        let ingredients = prop::collection::vec(0u64..250, 0..20);
+        (ranges, ingredients).prop_map(|(ranges, ingredients)| {
+            let ingredients: Vec<String> = ingredients.iter().map(|i| i.to_string()).collect();
+            format!("{}\n\n{}", ranges.join("\n"), ingredients.join("\n"))
+        })
+    }
+
+    fn count_every_id(input: &str) -> u64 {
+        let Input { ranges, .. } = parse(input).expect("valid input");
+        let max = ranges.iter().map(|r| *r.end()).max().unwrap_or(0);
+        (0..=max)
+            .filter(|id| ranges.iter().any(|r| r.contains(id)))
+            .count() as u64
+    }
+
+    #[test]
+    fn day5_against_reference() {
+        check::against_reference(small_inputs(), part_two, count_every_id);
+    }
+
+    #[test]
+    fn day5_fuzz_parse() {
+        crate::fuzz::parser(INPUT, parse);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{num::BigUint, par, parse::ParseError, runner::Part};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Mul,
+}
+
+pub struct Problem {
+    numbers: Vec<u64>,
+    op: Op,
+}
+
+fn parse_ops(idx: usize, line: &str) -> Result<Vec<Op>, ParseError> {
+    line.split_whitespace()
+        .map(|value| match value {
+            "+" => Ok(Op::Add),
+            "*" => Ok(Op::Mul),
+            c => Err(ParseError::at(idx, format!("Unknown op {c}"))),
+        })
+        .collect()
+}
+
+// Numbers read left to right, one per row
+pub fn parse(input: &str) -> Result<Vec<Problem>, ParseError> {
+    let mut lines: Vec<&str> = input.lines().collect();
+    let ops = lines
+        .pop()
+        .ok_or_else(|| ParseError::new(0, "to have ops"))?;
+    let ops = parse_ops(lines.len(), ops)?;
+
+    let numbers = lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            line.split_whitespace()
+                .map(|value| {
+                    value
+                        .parse::<u64>()
+                        .map_err(|_| ParseError::at(idx, "valid number"))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // sanity check
+    if let Some(idx) = numbers.iter().position(|n| n.len() != ops.len()) {
+        return Err(ParseError::at(
+            idx,
+            "all numbers list should be of the same length as the ops",
+        ));
+    }
+
+    Ok((0..ops.len())
+        .map(|idx| Problem {
+            numbers: numbers.iter().map(|n| n[idx]).collect(),
+            op: ops[idx],
+        })
+        .collect())
+}
+
+// Numbers read top to bottom, one per column. Problems are separated by columns
+// that are blank on every line.
+pub fn parse_columns(input: &str) -> Result<Vec<Problem>, ParseError> {
+    let mut lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    if let Some(idx) = lines.iter().position(|l| !l.is_ascii()) {
+        return Err(ParseError::at(idx, "columns only make sense in ASCII"));
+    }
+
+    let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+    let separator = |x: usize| lines.iter().all(|l| l.get(x).is_none_or(|c| *c == b' '));
+    // with an extra one past the end, to close the last problem
+    let separators: Vec<bool> = (0..width).map(separator).chain(Some(true)).collect();
+
+    let ops_line = lines
+        .pop()
+        .ok_or_else(|| ParseError::new(0, "to have ops"))?;
+    // checked to be ASCII
+    let ops_line = std::str::from_utf8(ops_line).expect("valid UTF-8");
+    let mut ops = parse_ops(lines.len(), ops_line)?.into_iter();
+
+    let mut problems = Vec::new();
+    let mut numbers = Vec::new();
+    for (x, separator) in separators.into_iter().enumerate() {
+        if separator {
+            if !numbers.is_empty() {
+                let op = ops
+                    .next()
+                    .ok_or_else(|| ParseError::new(0, "more problems than ops"))?;
+                let numbers = std::mem::take(&mut numbers);
+                problems.push(Problem { numbers, op });
+            }
+            continue;
+        }
+
+        let digits: String = lines
+            .iter()
+            .filter_map(|l| l.get(x))
+            .filter(|c| **c != b' ')
+            .map(|c| *c as char)
+            .collect();
+        let number = digits
+            .parse::<u64>()
+            .map_err(|_| ParseError::new(0, format!("no valid number in column {}", x + 1)))?;
+        numbers.push(number);
+    }
+
+    if ops.next().is_some() {
+        return Err(ParseError::new(0, "more ops than problems"));
+    }
+    Ok(problems)
+}
+
+// Products of a few large numbers are already past u64
+fn solve(problems: &[Problem]) -> BigUint {
+    par::map(problems, |p| {
+        let numbers = p.numbers.iter().map(|n| BigUint::from(*n));
+        match p.op {
+            Op::Add => numbers.sum::<BigUint>(),
+            Op::Mul => numbers.product(),
+        }
+    })
+    .into_iter()
+    .sum()
+}
+
+pub fn part_one(input: &str) -> BigUint {
+    let problems = parse(input).expect("valid input");
+    solve(&problems)
+}
+
+pub fn part_two(input: &str) -> BigUint {
+    let problems = parse_columns(input).expect("valid input");
+    solve(&problems)
+}
+
+pub const PARTS: &[Part] = crate::parts!(part_one < 10 ms, part_two < 10 ms);
+
+crate::examples!(day6);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const INPUT_A: &str = "123 328  51 64 ";
+    const INPUT_B: &str = " 45 64  387 23 ";
+    const INPUT_C: &str = "  6 98  215 314";
+    const INPUT_D: &str = "*   +   *   +  ";
+
+    // Spaces are actually important to the problem.
+    fn input() -> String {
+        [INPUT_A, INPUT_B, INPUT_C, INPUT_D].join("\n")
+    }
+
+    #[test]
+    fn day6() {
+        assert_eq!(part_one(&input()), BigUint::from(4277556u32));
+        assert_eq!(part_two(&input()), BigUint::from(3263827u32));
+    }
+
+    #[test]
+    fn day6_big() {
+        let input = "99999 99999 99999\n99999 99999 99999\n99999 99999 99999\n99999 99999 99999\n*     *     +    ";
+        let product = BigUint::from(99999u32).pow(4);
+        assert_eq!(part_one(input), &product * 2u32 + 99999u32 * 4u32);
+    }
+
+    #[test]
+    fn day6_parallel() {
+        let input = input();
+        let sequential = par::sequential(|| (part_one(&input), part_two(&input)));
+        assert_eq!((part_one(&input), part_two(&input)), sequential);
+    }
+
+    #[test]
+    fn day6_fuzz_parse() {
+        crate::fuzz::parser(&input(), parse);
+        crate::fuzz::parser(&input(), parse_columns);
+    }
+}
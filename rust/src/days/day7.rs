@@ -0,0 +1,197 @@
+use crate::{
+    bits::BitSet,
+    grid::Grid,
+    num::BigUint,
+    parse::ParseError,
+    runner::Part,
+    visualize::{self, Frame},
+};
+
+pub struct Input {
+    start: usize,
+    splitters: Vec<BitSet>,
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let mut lines = input.lines();
+    let first = lines
+        .next()
+        .ok_or_else(|| ParseError::new(0, "at least 1 line"))?;
+    let start = first
+        .char_indices()
+        .find_map(|(idx, c)| (c == 'S').then_some(idx))
+        .ok_or_else(|| ParseError::new(1, "to find the starting position"))?;
+
+    // Splitters need room on both sides, within the width of the first line
+    let width = first.len();
+    let splitters = lines
+        .enumerate()
+        .map(|(idx, line)| {
+            line.char_indices()
+                .filter_map(|(x, c)| match c {
+                    '^' if x == 0 || x + 1 >= width => {
+                        Some(Err(ParseError::at(idx + 1, "splitter on the edge")))
+                    }
+                    '^' => Some(Ok(x)),
+                    '.' => None,
+                    c => Some(Err(ParseError::at(idx + 1, format!("unexpected char {c}")))),
+                })
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Input { start, splitters })
+}
+
+pub fn part_one(input: &str) -> usize {
+    let Input { start, splitters } = parse(input).expect("valid input");
+
+    let mut tachyons = BitSet::new();
+    tachyons.insert(start);
+
+    let mut picture = visualize::recording().then(|| Grid::parse(input, |c| c));
+
+    let mut count = 0;
+    for (row, splitters) in splitters.into_iter().enumerate() {
+        let remove = &tachyons & &splitters;
+        let new: BitSet = remove.iter().flat_map(|x| [x - 1, x + 1]).collect();
+
+        count += remove.len();
+
+        if let Some(picture) = &mut picture {
+            let hit: Vec<_> = remove.iter().map(|x| (x, row + 1)).collect();
+            for x in tachyons.iter().chain(new.iter()) {
+                if picture[(x, row + 1)] == '.' {
+                    picture[(x, row + 1)] = '|';
+                }
+            }
+            visualize::emit(|| {
+                Frame::new(format!("row {}, {count} splits", row + 1), picture.clone())
+                    .highlight(hit)
+            });
+        }
+
+        tachyons = &(&tachyons | &new) - &remove;
+    }
+
+    count
+}
+
+// Timelines per column, row by row, a splitter sending its count to both sides. They
+// can double on every row, one row of counts is kept at a time.
+pub fn part_two(input: &str) -> BigUint {
+    let Input { start, splitters } = parse(input).expect("valid input");
+    if visualize::recording() {
+        emit_timelines(input, start, &splitters);
+    }
+
+    // Splitters have a column on each side
+    let width = splitters
+        .iter()
+        .flat_map(BitSet::iter)
+        .map(|x| x + 2)
+        .fold(start + 1, usize::max);
+    let mut counts = vec![BigUint::ZERO; width];
+    counts[start] = BigUint::from(1u8);
+    for splitters in &splitters {
+        let mut next = vec![BigUint::ZERO; width];
+        for (x, count) in counts.iter_mut().enumerate() {
+            let count = std::mem::take(count);
+            if splitters.contains(x) {
+                next[x - 1] += &count;
+                next[x + 1] += count;
+            } else {
+                next[x] += count;
+            }
+        }
+        counts = next;
+    }
+    counts.into_iter().sum()
+}
+
+// Timelines per column, row by row, as a heatmap
+fn emit_timelines(input: &str, start: usize, splitters: &[BitSet]) {
+    let picture = Grid::parse(input, |c| c);
+    let mut counts = vec![0u64; picture.width()];
+    counts[start] = 1;
+
+    let mut rows = Vec::new();
+    for splitters in splitters {
+        let mut next = vec![0u64; counts.len()];
+        for (x, count) in counts.iter().enumerate() {
+            // Only drawn, they can saturate
+            if splitters.contains(x) {
+                next[x - 1] = next[x - 1].saturating_add(*count);
+                next[x + 1] = next[x + 1].saturating_add(*count);
+            } else {
+                next[x] = next[x].saturating_add(*count);
+            }
+        }
+        counts = next;
+        rows.push(counts.clone());
+    }
+
+    // Log scale, the counts explode further down
+    let max = rows.iter().flatten().max().copied().unwrap_or(1) as f32;
+    let mut heat = picture.map(|_| 0.0);
+    for (row, counts) in rows.iter().enumerate() {
+        for (x, count) in counts.iter().enumerate() {
+            heat[(x, row + 1)] = (*count as f32).ln_1p() / max.ln_1p();
+        }
+        visualize::emit(|| {
+            let total = counts
+                .iter()
+                .fold(0u64, |total, n| total.saturating_add(*n));
+            let caption = format!("row {}, {total} timelines", row + 1);
+            Frame::new(caption, picture.clone()).heat(heat.clone())
+        });
+    }
+}
+
+pub const PARTS: &[Part] = crate::parts!(part_one < 20 ms, part_two < 100 ms);
+
+crate::examples!(day7);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const INPUT: &str = ".......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............";
+
+    #[test]
+    fn day7() {
+        assert_eq!(part_one(INPUT), 21);
+        assert_eq!(part_two(INPUT), BigUint::from(40u8));
+    }
+
+    #[test]
+    fn day7_edges() {
+        assert!(parse(".S.\n...\n.^.").is_ok());
+        // A start on the edge is fine as long as no splitter is
+        assert_eq!(part_two("S..\n..."), BigUint::from(1u8));
+        for input in [".S.\n^..", ".S.\n..^", ".S.\n....^"] {
+            let err = parse(input).err().expect("an error");
+            assert!(err.to_string().contains("splitter on the edge"), "{err}");
+        }
+    }
+
+    #[test]
+    fn day7_fuzz_parse() {
+        crate::fuzz::parser(INPUT, parse);
+    }
+}
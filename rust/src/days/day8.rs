@@ -0,0 +1,176 @@
+use std::collections::BinaryHeap;
+
+use crate::{
+    bytes,
+    graph::mst::{self, Edge},
+    par,
+    parse::ParseError,
+    runner::Part,
+};
+
+#[derive(Debug)]
+pub struct Coords {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Coords {
+    pub fn dist(&self, other: &Coords) -> i64 {
+        let x = (self.x - other.x) * (self.x - other.x);
+        let y = (self.y - other.y) * (self.y - other.y);
+        let z = (self.z - other.z) * (self.z - other.z);
+        i64::isqrt(x + y + z)
+    }
+}
+
+struct Top {
+    list: BinaryHeap<Edge<i64>>,
+    maxlen: usize,
+}
+
+impl Top {
+    fn new(maxlen: usize) -> Self {
+        Top {
+            list: BinaryHeap::new(), // Can't use with_capacity, usize::MAX is too big
+            maxlen,
+        }
+    }
+
+    fn insert(&mut self, val: Edge<i64>) {
+        if self.list.len() < self.maxlen {
+            self.list.push(val);
+            return;
+        }
+
+        let peeked = self.list.peek().expect("some values in the heap");
+        if *peeked > val {
+            let _ = self.list.pop();
+            self.list.push(val);
+        }
+    }
+
+    fn into_sorted_vec(self) -> Vec<Edge<i64>> {
+        self.list.into_sorted_vec()
+    }
+}
+
+// Keeps squared distances within i64
+const MAX_COORD: i64 = 1 << 29;
+
+pub fn parse(input: &[u8]) -> Result<Vec<Coords>, ParseError> {
+    bytes::lines(input)
+        .enumerate()
+        .map(|(idx, line)| {
+            let coords = line
+                .split(|b| *b == b',')
+                .map(|x| bytes::int::<i64>(x).filter(|x| x.abs() <= MAX_COORD))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| ParseError::at(idx, "valid number"))?;
+            let [x, y, z] = coords[..] else {
+                return Err(ParseError::at(idx, "3 coords"));
+            };
+            Ok(Coords { x, y, z })
+        })
+        .collect()
+}
+
+pub fn part_one(input: &[u8]) -> usize {
+    solve_part_one(input, 1000)
+}
+
+fn solve_part_one(input: &[u8], maxlen: usize) -> usize {
+    let coords = parse(input).expect("valid input");
+    let edges = shortest_connections(&coords, maxlen);
+    let circuits = mst::components_after(coords.len(), edges, maxlen);
+    circuits.sizes().iter().take(3).product()
+}
+
+// The connection making it one big circuit is the last one of the spanning tree
+pub fn part_two(input: &[u8]) -> i64 {
+    let coords = parse(input).expect("valid input");
+    let edges = shortest_connections(&coords, usize::MAX);
+    let tree = mst::kruskal(coords.len(), edges);
+    let edge = tree.completing.expect("It was never one big circuit");
+    coords[edge.a].x * coords[edge.b].x
+}
+
+fn shortest_connections(coords: &[Coords], maxlen: usize) -> Vec<Edge<i64>> {
+    let len = coords.len();
+    let rows: Vec<usize> = (0..len).collect();
+    let tops = par::map(&rows, |a| {
+        let mut top = Top::new(maxlen);
+        for b in a + 1..len {
+            let weight = coords[*a].dist(&coords[b]);
+            top.insert(Edge { weight, a: *a, b });
+        }
+        top
+    });
+
+    let mut top = Top::new(maxlen);
+    for row in tops {
+        for distance in row.list {
+            top.insert(distance);
+        }
+    }
+    top.into_sorted_vec()
+}
+
+pub const PARTS: &[Part] = crate::parts!(part_one < 200 ms, part_two < 300 ms);
+
+crate::examples!(day8);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::check::{self, proptest::prelude::*};
+
+    const INPUT: &str = "162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689";
+
+    #[test]
+    fn day8() {
+        assert_eq!(solve_part_one(INPUT.as_bytes(), 10), 40);
+        assert_eq!(part_two(INPUT.as_bytes()), 25272);
+    }
+
+    #[test]
+    fn day8_parallel() {
+        let coords =
+            (0..1000i64, 0..1000i64, 0..1000i64).prop_map(|(x, y, z)| format!("{x},{y},{z}"));
+        let coords = prop::collection::vec(coords, 2..40).prop_map(|l| l.join("\n"));
+        check::against_reference(
+            coords,
+            |input| part_two(input.as_bytes()),
+            |input| par::sequential(|| part_two(input.as_bytes())),
+        );
+        let input = INPUT.as_bytes();
+        assert_eq!(
+            solve_part_one(input, 10),
+            par::sequential(|| solve_part_one(input, 10))
+        );
+    }
+
+    #[test]
+    fn day8_fuzz_parse() {
+        crate::fuzz::parser(INPUT, |input| parse(input.as_bytes()));
+    }
+}
@@ -0,0 +1,90 @@
+/// Circular dial with `size` positions, numbered from `0` to `size - 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    size: i64,
+    position: i64,
+}
+
+impl Dial {
+    pub fn new(size: i64, position: i64) -> Self {
+        assert!(size > 0, "a dial needs at least one position");
+        Dial {
+            size,
+            position: position.rem_euclid(size),
+        }
+    }
+
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    /// Turns the dial, right for positive clicks and left for negative ones.
+    pub fn rotate(&mut self, clicks: i64) {
+        self.position = (self.position + clicks).rem_euclid(self.size);
+    }
+
+    /// How many of the clicks of a `clicks` rotation would leave the dial on `target`,
+    /// landing on it at the end included.
+    pub fn passes(&self, clicks: i64, target: i64) -> i64 {
+        // Shift so that target is 0, then count the multiples of size crossed
+        let from = self.position - target.rem_euclid(self.size);
+        let to = from + clicks;
+        match clicks.signum() {
+            -1 => (from - 1).div_euclid(self.size) - (to - 1).div_euclid(self.size),
+            _ => to.div_euclid(self.size) - from.div_euclid(self.size),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn click_by_click(mut dial: Dial, clicks: i64, target: i64) -> i64 {
+        let mut passes = 0;
+        for _ in 0..clicks.abs() {
+            dial.rotate(clicks.signum());
+            if dial.position() == target.rem_euclid(dial.size) {
+                passes += 1;
+            }
+        }
+        passes
+    }
+
+    #[test]
+    fn dial() {
+        let mut dial = Dial::new(100, 50);
+        assert_eq!(dial.passes(-68, 0), 1);
+        dial.rotate(-68);
+        assert_eq!(dial.position(), 82);
+        assert_eq!(dial.passes(18, 0), 1);
+        assert_eq!(dial.passes(1000, 0), 10);
+        assert_eq!(dial.passes(0, 82), 0);
+    }
+
+    #[cfg(feature = "alloc-profile")]
+    #[test]
+    fn dial_no_alloc() {
+        let mut dial = Dial::new(100, 50);
+        crate::alloc::assert_no_alloc(|| {
+            for clicks in [-68, 30, 1000, -1000] {
+                dial.passes(clicks, 0);
+                dial.rotate(clicks);
+            }
+        });
+    }
+
+    proptest! {
+        #[test]
+        fn dial_passes(
+            size in 1i64..300,
+            position in -1000i64..1000,
+            clicks in -2000i64..2000,
+            target in -1000i64..1000,
+        ) {
+            let dial = Dial::new(size, position);
+            prop_assert_eq!(dial.passes(clicks, target), click_by_click(dial, clicks, target));
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Example inputs as files: `examples/dayN/<name>.txt` next to `<name>.expected`.
+//!
+//! The expected file has one `part_one: <answer>` line per part to check, the build
+//! script turns every one of them into a test of the day's module, named
+//! `examples::<name>_<part>`, as long as the module calls [`crate::examples!`].
+use crate::{input::Input, runner::FromInput, Answer};
+
+/// `(part, answer)` pairs of an expected answers file.
+pub fn parse_expected(expected: &str) -> Vec<(&str, &str)> {
+    expected
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(part, answer)| (part.trim(), answer.trim()))
+        .collect()
+}
+
+/// Panics with a line by line diff if `part` doesn't answer `expected` on `input`.
+///
+/// Answers are compared with [`Answer::matches`], so `0042` is the same as `42` and
+/// art the same as the letters it draws.
+///
+/// The global caches are left alone, other tests run parts at the same time: parts
+/// memoize per call with [`Scoped`](crate::cache::Scoped) caches, and the global
+/// ones only hold pure functions, so answers don't depend on what's in them.
+pub fn check<'a, I, T>(part: impl Fn(I) -> T, input: &'a str, expected: &str)
+where
+    I: FromInput<'a>,
+    T: Into<Answer>,
+{
+    let actual: Answer = part(I::from_input(&Input::from(input))).into();
+    if actual.matches(expected) {
+        return;
+    }
+    let actual = actual.to_string();
+
+    let mut diff = String::new();
+    let expected: Vec<&str> = expected.trim().lines().collect();
+    let actual: Vec<&str> = actual.trim().lines().collect();
+    for idx in 0..expected.len().max(actual.len()) {
+        match (expected.get(idx), actual.get(idx)) {
+            (Some(e), Some(a)) if e == a => diff.push_str(&format!("  {e}\n")),
+            (e, a) => {
+                if let Some(e) = e {
+                    diff.push_str(&format!("- {e}\n"));
+                }
+                if let Some(a) = a {
+                    diff.push_str(&format!("+ {a}\n"));
+                }
+            }
+        }
+    }
+    panic!("wrong answer (- expected, + actual):\n{diff}");
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn examples_check() {
+        check(|input: &str| input.len(), "abc", "3\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "- 4\n+ 3")]
+    fn examples_check_diff() {
+        check(|input: &str| input.len(), "abc", "4");
+    }
+}
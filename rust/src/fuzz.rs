@@ -0,0 +1,119 @@
+//! In-tree fuzz driver for the days' parsers.
+//!
+//! Throws random strings, random bytes from the example's alphabet and mutated examples
+//! at a parser, which must return either a value or a [`ParseError`]: panics (overflows
+//! included, in debug builds) fail the run, and so does a parser taking longer than
+//! [`TIMEOUT`] overall. Set `PROPTEST_CASES` for longer runs.
+use std::{
+    panic::RefUnwindSafe,
+    sync::{mpsc, Arc, Mutex},
+    time::Duration,
+};
+
+use proptest::{
+    prelude::*,
+    test_runner::{Config, TestRunner},
+};
+
+use crate::{check, parse::ParseError};
+
+pub const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Bytes that tend to upset hand-written parsers.
+const TROUBLE: &[u8] = b"\n\r\t -,+0123456789\xc3\xa9\xff";
+
+fn lossy(bytes: Vec<u8>) -> String {
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+pub fn inputs(example: &str) -> impl Strategy<Value = String> {
+    let example = example.as_bytes().to_vec();
+    let mut alphabet = example.clone();
+    alphabet.extend_from_slice(TROUBLE);
+    alphabet.sort_unstable();
+    alphabet.dedup();
+
+    // (where, byte, replace/insert/delete)
+    let edit = (any::<prop::sample::Index>(), any::<u8>(), 0..3u8);
+    prop_oneof![
+        any::<String>(),
+        prop::collection::vec(prop::sample::select(alphabet), 0..300).prop_map(lossy),
+        prop::collection::vec(edit, 1..10).prop_map(move |edits| {
+            let mut bytes = example.clone();
+            for (idx, byte, kind) in edits {
+                let len = bytes.len();
+                match kind {
+                    _ if len == 0 => bytes.push(byte),
+                    0 => bytes[idx.index(len)] = byte,
+                    1 => bytes.insert(idx.index(len + 1), byte),
+                    _ => {
+                        bytes.remove(idx.index(len));
+                    }
+                }
+            }
+            lossy(bytes)
+        }),
+    ]
+}
+
+/// Fuzzes `parse`, starting from a valid `example` input.
+pub fn parser<T, F>(example: &str, parse: F)
+where
+    F: Fn(&str) -> Result<T, ParseError> + RefUnwindSafe + Send + 'static,
+{
+    assert!(parse(example).is_ok(), "the example should parse");
+
+    let inputs = inputs(example);
+    let current = Arc::new(Mutex::new(String::new()));
+    let (done, finished) = mpsc::channel();
+
+    let watched = current.clone();
+    std::thread::spawn(move || {
+        let config = Config {
+            cases: check::cases(),
+            failure_persistence: None,
+            ..Config::default()
+        };
+        let mut runner = TestRunner::new(config);
+        let result = runner.run(&inputs, |input| {
+            watched.lock().expect("current input").clone_from(&input);
+            // A panic here is caught and shrunk by the runner
+            let _ = parse(&input);
+            Ok(())
+        });
+        let _ = done.send(result.map_err(|err| err.to_string()));
+    });
+
+    match finished.recv_timeout(TIMEOUT) {
+        Ok(Ok(())) => {}
+        Ok(Err(err)) => panic!("{err}"),
+        Err(_) => {
+            let input = current.lock().map(|i| i.clone()).unwrap_or_default();
+            panic!("parser still running after {TIMEOUT:?}, last input:\n{input:?}");
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn numbers(input: &str) -> Result<Vec<u32>, ParseError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, l)| l.parse().map_err(|_| ParseError::at(idx, "not a number")))
+            .collect()
+    }
+
+    #[test]
+    fn fuzz_parser() {
+        parser("1\n22\n333", numbers);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fuzz_parser_panics() {
+        parser("1\n22\n333", |input| Ok(numbers(input).expect("valid")));
+    }
+}
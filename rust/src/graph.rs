@@ -0,0 +1,349 @@
+//! Searches over implicit graphs: a node type and a closure giving the neighbours of
+//! a node, with their edge cost for the weighted searches.
+//!
+//! On a [`Grid`](crate::grid::Grid), positions are the nodes and
+//! [`Grid::adjacent`](crate::grid::Grid::adjacent), filtered on the cells that can be
+//! walked on, the neighbours:
+//!
+//! ```ignore
+//! let paths = graph::bfs(start, |pos| grid.adjacent(*pos, &VON_NEUMANN).filter(|p| grid[*p] != '#'));
+//! ```
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+pub mod mst;
+
+/// What a search reached from its start: the cost of the best path to every node,
+/// and the way back.
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    start: N,
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Paths<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Paths {
+            costs: HashMap::from([(start.clone(), zero)]),
+            start,
+            parents: HashMap::new(),
+        }
+    }
+
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// Every reached node, with its cost, in no particular order.
+    pub fn costs(&self) -> impl Iterator<Item = (&N, C)> {
+        self.costs.iter().map(|(node, cost)| (node, *cost))
+    }
+
+    /// The nodes of a best path from the start to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.costs.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last().expect("not empty")) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth first search, the cost being the number of edges.
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Paths<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let cost = paths.costs[&node] + 1;
+        for next in neighbours(&node) {
+            if !paths.costs.contains_key(&next) {
+                paths.costs.insert(next.clone(), cost);
+                paths.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    paths
+}
+
+/// Depth first search, the nodes reachable from `start` in the order they're first
+/// visited.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        let mut next: Vec<N> = neighbours(&node)
+            .into_iter()
+            .filter(|n| !seen.contains(n))
+            .collect();
+        // So that the first neighbour is visited first
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+    order
+}
+
+/// Dijkstra's shortest paths to every node reachable from `start`. Costs can't be
+/// negative, `C::default()` is the cost of the empty path.
+pub fn dijkstra<N, C, I>(start: N, neighbours: impl FnMut(&N) -> I) -> Paths<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    search(start, neighbours, |_| C::default(), |_| false).0
+}
+
+/// A* search of the shortest path from `start` to a node satisfying `goal`, returning
+/// that node. `heuristic` must never overestimate the cost left to a goal. It doesn't
+/// need to be consistent, nodes reached again by a cheaper path are expanded again.
+pub fn astar<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(N, Paths<N, C>)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (paths, found) = search(start, neighbours, heuristic, goal);
+    found.map(|node| (node, paths))
+}
+
+fn search<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Paths<N, C>, Option<N>)
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new(start.clone(), C::default());
+    // Nodes are only compared by cost, through their index in `queued`, along with
+    // the cost they were queued with
+    let mut queued = vec![(start.clone(), C::default())];
+    let mut queue = BinaryHeap::from([(Reverse(heuristic(&start)), Reverse(0))]);
+
+    while let Some((_, Reverse(idx))) = queue.pop() {
+        let (node, cost) = queued[idx].clone();
+        // Queued again since, with a cheaper path. Nodes aren't closed once expanded: a
+        // heuristic that isn't consistent can still find a cheaper path to them later.
+        if paths.costs[&node] < cost {
+            continue;
+        }
+        if goal(&node) {
+            return (paths, Some(node));
+        }
+
+        for (next, edge) in neighbours(&node) {
+            let next_cost = cost + edge;
+            if paths.cost(&next).is_some_and(|c| c <= next_cost) {
+                continue;
+            }
+            paths.costs.insert(next.clone(), next_cost);
+            paths.parents.insert(next.clone(), node.clone());
+            queue.push((Reverse(next_cost + heuristic(&next)), Reverse(queued.len())));
+            queued.push((next, next_cost));
+        }
+    }
+    (paths, None)
+}
+
+/// Orders `nodes` and everything reachable from them so that every node comes
+/// before its neighbours. Fails with a node on a cycle.
+pub fn toposort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    // Nodes on the current path are `false`, finished ones `true`
+    let mut state: HashMap<N, bool> = HashMap::new();
+    let mut order = Vec::new();
+    for root in nodes {
+        if state.contains_key(&root) {
+            continue;
+        }
+        state.insert(root.clone(), false);
+        let mut stack = vec![(root.clone(), neighbours(&root).into_iter())];
+        while let Some((node, next)) = stack.last_mut() {
+            match next.next() {
+                Some(next) => match state.get(&next) {
+                    Some(false) => return Err(next),
+                    Some(true) => {}
+                    None => {
+                        state.insert(next.clone(), false);
+                        let after = neighbours(&next).into_iter();
+                        stack.push((next, after));
+                    }
+                },
+                None => {
+                    state.insert(node.clone(), true);
+                    order.push(node.clone());
+                    stack.pop();
+                }
+            }
+        }
+    }
+    order.reverse();
+    Ok(order)
+}
+
+/// Connected components of the graph made of `nodes` and everything reachable from
+/// them, `neighbours` going both ways along every edge.
+pub fn components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+        let component = dfs(node, &mut neighbours);
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+    components
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::grid::{Grid, Pos, VON_NEUMANN};
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.####.#.
+......#E";
+
+    fn maze() -> (Grid<char>, Pos, Pos) {
+        let grid = Grid::parse(MAZE, |c| c);
+        let find = |c| grid.iter().find(|(_, v)| **v == c).unwrap().0;
+        let (start, end) = (find('S'), find('E'));
+        (grid, start, end)
+    }
+
+    #[test]
+    fn graph_bfs() {
+        let (grid, start, end) = maze();
+        let walk = |pos: &Pos| {
+            let grid = &grid;
+            grid.adjacent(*pos, &VON_NEUMANN)
+                .filter(|p| grid[*p] != '#')
+        };
+        let paths = bfs(start, walk);
+        assert_eq!(paths.cost(&end), Some(15));
+        let path = paths.path_to(&end).unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!((path[0], path[15]), (start, end));
+        assert!(path
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+        assert_eq!(paths.cost(&(2, 0)), None);
+        assert_eq!(paths.path_to(&(2, 0)), None);
+    }
+
+    #[test]
+    fn graph_weighted() {
+        let (grid, start, end) = maze();
+        // Walking on the bottom row costs 5 per step
+        let walk = |pos: &Pos| {
+            let grid = &grid;
+            grid.adjacent(*pos, &VON_NEUMANN)
+                .filter(|p| grid[*p] != '#')
+                .map(|p| (p, if p.1 == 4 { 5 } else { 1 }))
+        };
+        let paths = dijkstra(start, walk);
+        assert_eq!(paths.cost(&end), Some(19));
+
+        let manhattan = |pos: &Pos| end.0.abs_diff(pos.0) + end.1.abs_diff(pos.1);
+        let (found, astar_paths) = astar(start, walk, manhattan, |pos| *pos == end).unwrap();
+        assert_eq!(found, end);
+        assert_eq!(astar_paths.cost(&end), Some(19));
+        assert_eq!(astar_paths.path_to(&end), paths.path_to(&end));
+
+        assert!(astar(start, walk, |_| 0, |pos| *pos == (2, 0)).is_none());
+    }
+
+    #[test]
+    fn graph_inconsistent_heuristic() {
+        // Overestimates from A compared to its neighbour C, but not up to the goal
+        let edges = HashMap::from([
+            ("S", vec![("A", 1), ("B", 1)]),
+            ("A", vec![("C", 1)]),
+            ("B", vec![("C", 3)]),
+            ("C", vec![("G", 3)]),
+            ("G", vec![]),
+        ]);
+        let next = |n: &&str| edges[n].clone();
+        let heuristic = |n: &&str| if *n == "A" { 4 } else { 0 };
+        let (goal, paths) = astar("S", next, heuristic, |n| *n == "G").unwrap();
+        assert_eq!(goal, "G");
+        assert_eq!(paths.cost(&"G"), Some(5));
+        assert_eq!(paths.path_to(&"G").unwrap(), ["S", "A", "C", "G"]);
+    }
+
+    #[test]
+    fn graph_dfs() {
+        let edges = HashMap::from([(1, vec![2, 3]), (2, vec![4]), (3, vec![4]), (4, vec![])]);
+        let next = |n: &i32| edges[n].clone();
+        assert_eq!(dfs(1, next), vec![1, 2, 4, 3]);
+        assert_eq!(toposort([4, 1], next), Ok(vec![1, 3, 2, 4]));
+
+        let cycle = |n: &i32| vec![(n + 1) % 3];
+        assert!(toposort([0], cycle).is_err());
+    }
+
+    #[test]
+    fn graph_components() {
+        let edges = HashMap::from([
+            (1, vec![2]),
+            (2, vec![1]),
+            (3, vec![]),
+            (4, vec![5]),
+            (5, vec![4]),
+        ]);
+        let mut components = components(1..=5, |n: &i32| edges[n].clone());
+        components.iter_mut().for_each(|c| c.sort());
+        assert_eq!(components, vec![vec![1, 2], vec![3], vec![4, 5]]);
+    }
+}
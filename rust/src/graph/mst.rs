@@ -0,0 +1,234 @@
+//! Minimum spanning trees, over nodes numbered `0..nodes`.
+use std::ops::Add;
+
+/// Ordered by weight, then by nodes so that ties don't depend on the input order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Edge<W> {
+    pub weight: W,
+    pub a: usize,
+    pub b: usize,
+}
+
+/// Disjoint sets of `0..len`, merged by size with path halving.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    pub fn new(len: usize) -> Self {
+        UnionFind {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            components: len,
+        }
+    }
+
+    /// The representative of the set of `node`.
+    pub fn find(&mut self, mut node: usize) -> usize {
+        while self.parents[node] != node {
+            self.parents[node] = self.parents[self.parents[node]];
+            node = self.parents[node];
+        }
+        node
+    }
+
+    /// Merges the sets of `a` and `b`, `false` if they already were the same.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.components -= 1;
+        true
+    }
+
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// The size of every set, largest first.
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.parents.len())
+            .filter(|node| self.parents[*node] == *node)
+            .map(|root| self.sizes[root])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tree<W> {
+    /// In the order they were added
+    pub edges: Vec<Edge<W>>,
+    pub weight: W,
+    /// The heaviest edge, the one Kruskal's adds last to connect everything. `None`
+    /// when the graph isn't connected, then `edges` is a spanning forest.
+    pub completing: Option<Edge<W>>,
+}
+
+impl<W: Copy + Ord + Add<Output = W> + Default> Tree<W> {
+    fn new(nodes: usize, edges: Vec<Edge<W>>) -> Self {
+        let connected = edges.len() + 1 == nodes;
+        Tree {
+            weight: edges.iter().fold(W::default(), |sum, e| sum + e.weight),
+            completing: edges.iter().max().copied().filter(|_| connected),
+            edges,
+        }
+    }
+}
+
+/// Kruskal's: adds the edges from the lightest, skipping the ones closing a cycle.
+pub fn kruskal<W>(nodes: usize, edges: impl IntoIterator<Item = Edge<W>>) -> Tree<W>
+where
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    let mut edges: Vec<Edge<W>> = edges.into_iter().collect();
+    edges.sort_unstable();
+
+    let mut sets = UnionFind::new(nodes);
+    let mut tree = Vec::new();
+    for edge in edges {
+        if sets.components() <= 1 {
+            break;
+        }
+        if sets.union(edge.a, edge.b) {
+            tree.push(edge);
+        }
+    }
+    Tree::new(nodes, tree)
+}
+
+/// Prim's, for dense graphs: `weight(a, b)` of the edge between two nodes, if any.
+/// Grows the tree from node 0, in `O(nodes²)` without a single edge list.
+pub fn prim<W>(nodes: usize, mut weight: impl FnMut(usize, usize) -> Option<W>) -> Tree<W>
+where
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    let mut in_tree = vec![false; nodes];
+    // The lightest edge from the tree to every node outside of it
+    let mut best: Vec<Option<Edge<W>>> = vec![None; nodes];
+    let mut tree = Vec::new();
+    let mut next = (nodes > 0).then_some(0);
+
+    while let Some(node) = next {
+        in_tree[node] = true;
+        for other in (0..nodes).filter(|other| !in_tree[*other]) {
+            let Some(w) = weight(node, other) else {
+                continue;
+            };
+            let edge = Edge {
+                weight: w,
+                a: node.min(other),
+                b: node.max(other),
+            };
+            if best[other].is_none_or(|b| edge < b) {
+                best[other] = Some(edge);
+            }
+        }
+
+        next = (0..nodes)
+            .filter(|other| !in_tree[*other])
+            .filter_map(|other| best[other].map(|edge| (edge, other)))
+            .min()
+            .map(|(edge, other)| {
+                tree.push(edge);
+                other
+            });
+    }
+    Tree::new(nodes, tree)
+}
+
+/// The sets of nodes connected by the `k` lightest edges, cycles or not.
+pub fn components_after<W: Ord>(
+    nodes: usize,
+    edges: impl IntoIterator<Item = Edge<W>>,
+    k: usize,
+) -> UnionFind {
+    let mut edges: Vec<Edge<W>> = edges.into_iter().collect();
+    edges.sort_unstable();
+
+    let mut sets = UnionFind::new(nodes);
+    for edge in edges.iter().take(k) {
+        sets.union(edge.a, edge.b);
+    }
+    sets
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn edge(a: usize, b: usize, weight: u32) -> Edge<u32> {
+        Edge { weight, a, b }
+    }
+
+    #[test]
+    fn mst_small() {
+        // A square with a diagonal, and a node on its own
+        let edges = [
+            edge(0, 1, 1),
+            edge(1, 2, 2),
+            edge(2, 3, 1),
+            edge(0, 3, 4),
+            edge(0, 2, 3),
+        ];
+        let tree = kruskal(4, edges);
+        assert_eq!(tree.weight, 4);
+        assert_eq!(tree.completing, Some(edge(1, 2, 2)));
+        assert_eq!(tree.edges.len(), 3);
+
+        let weight = |a: usize, b: usize| {
+            edges
+                .iter()
+                .find(|e| (e.a, e.b) == (a.min(b), a.max(b)))
+                .map(|e| e.weight)
+        };
+        let mut prim = prim(4, weight);
+        assert_eq!(
+            (prim.weight, prim.completing),
+            (tree.weight, tree.completing)
+        );
+        prim.edges.sort();
+        assert_eq!(prim.edges, [edge(0, 1, 1), edge(2, 3, 1), edge(1, 2, 2)]);
+
+        let forest = kruskal(5, edges);
+        assert_eq!(forest.weight, 4);
+        assert_eq!(forest.completing, None);
+
+        let sets = components_after(5, edges, 2);
+        assert_eq!(sets.components(), 3);
+        assert_eq!(sets.sizes(), vec![2, 2, 1]);
+    }
+
+    proptest! {
+        #[test]
+        fn mst_kruskal_prim(points in prop::collection::vec((0..50u32, 0..50u32), 1..30)) {
+            let weight = |a: usize, b: usize| {
+                let ((xa, ya), (xb, yb)) = (points[a], points[b]);
+                Some(xa.abs_diff(xb) + ya.abs_diff(yb))
+            };
+            let nodes = points.len();
+            let edges = (0..nodes)
+                .flat_map(|a| (a + 1..nodes).map(move |b| (a, b)))
+                .map(|(a, b)| edge(a, b, weight(a, b).unwrap()));
+            let kruskal = kruskal(nodes, edges);
+            let prim = prim(nodes, weight);
+            prop_assert_eq!(kruskal.weight, prim.weight);
+            prop_assert_eq!(kruskal.edges.len(), nodes - 1);
+            prop_assert_eq!(
+                kruskal.completing.map(|e| e.weight),
+                prim.completing.map(|e| e.weight)
+            );
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use std::ops::{Index, IndexMut};
+
+use crate::parse::ParseError;
+
+pub type Pos = (usize, usize);
+
+/// The 8 cells surrounding a position.
+pub const MOORE: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// The 4 orthogonally adjacent cells.
+pub const VON_NEUMANN: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Dense row-major 2D grid, indexed by `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from the lines of `input`, mapping every char with `f`.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        Self::try_parse(input, |c| Ok(f(c))).expect("valid grid")
+    }
+
+    /// Same as [`Grid::parse`], `f` can reject chars with a reason.
+    pub fn try_parse(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<T, String>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (idx, line) in input.lines().enumerate() {
+            let before = cells.len();
+            for c in line.chars() {
+                cells.push(f(c).map_err(|reason| ParseError::at(idx, reason))?);
+            }
+            let len = cells.len() - before;
+            if *width.get_or_insert(len) != len {
+                return Err(ParseError::at(
+                    idx,
+                    "all lines should be of the same length",
+                ));
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Positions reachable from `pos` through `offsets`, skipping the ones out of bounds.
+    pub fn adjacent<'a>(
+        &self,
+        (x, y): Pos,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let xx = x.checked_add_signed(*dx).filter(|xx| *xx < width)?;
+            let yy = y.checked_add_signed(*dy).filter(|yy| *yy < height)?;
+            Some((xx, yy))
+        })
+    }
+
+    pub fn neighbours<'a>(&'a self, pos: Pos, offsets: &'a [(isize, isize)]) -> Neighbours<'a, T> {
+        Neighbours {
+            grid: self,
+            pos,
+            offsets: offsets.iter(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Pos) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) out of bounds"
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Pos) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) out of bounds"
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Values of the in-bounds cells around a position, see [`Grid::neighbours`].
+pub struct Neighbours<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Pos,
+    offsets: std::slice::Iter<'a, (isize, isize)>,
+}
+
+impl<'a, T> Iterator for Neighbours<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let (x, y) = self.pos;
+        for (dx, dy) in self.offsets.by_ref() {
+            let (Some(xx), Some(yy)) = (x.checked_add_signed(*dx), y.checked_add_signed(*dy))
+            else {
+                continue;
+            };
+            if let Some(value) = self.grid.get((xx, yy)) {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
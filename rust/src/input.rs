@@ -0,0 +1,188 @@
+//! Where the runner gets a day's input from.
+//!
+//! By default the whole file is read into memory. With `--mmap` it's memory-mapped
+//! instead, and with `--stream` the parts that only need one pass over the lines
+//! (declared with `streaming` in [`crate::parts!`]) read it line by line, so that
+//! stress inputs of several GB don't have to fit in memory.
+use std::{
+    cell::OnceCell,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    marker::PhantomData,
+    path::Path,
+};
+
+use memmap2::Mmap;
+
+use crate::bytes;
+
+/// The whole input file, as bytes: it's only checked to be UTF-8 by the parts that
+/// take it as `&str`, see [`Input`].
+pub enum Source {
+    Read(Vec<u8>),
+    Mapped(Mmap),
+}
+
+impl Source {
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut input = Vec::new();
+        File::open(path)?.read_to_end(&mut input)?;
+        Ok(Source::Read(input))
+    }
+
+    /// Maps the file, it must not change while mapped.
+    pub fn map(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: inputs aren't written to while a day runs
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(Source::Mapped(map))
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            Source::Read(input) => input,
+            Source::Mapped(map) => map,
+        }
+    }
+}
+
+/// A whole input handed to the parts, see [`crate::runner::FromInput`]. The parts
+/// share it, so it's checked to be UTF-8 at most once, by the first one taking `&str`.
+pub struct Input<'a> {
+    bytes: &'a [u8],
+    text: OnceCell<&'a str>,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Input {
+            bytes,
+            text: OnceCell::new(),
+        }
+    }
+
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Panics if the input isn't UTF-8, which the runner reports for the part.
+    pub fn text(&self) -> &'a str {
+        self.text.get_or_init(|| {
+            std::str::from_utf8(self.bytes).unwrap_or_else(|err| panic!("Input isn't UTF-8: {err}"))
+        })
+    }
+}
+
+impl<'a> From<&'a str> for Input<'a> {
+    fn from(text: &'a str) -> Self {
+        Input {
+            bytes: text.as_bytes(),
+            text: OnceCell::from(text),
+        }
+    }
+}
+
+/// Lines, one at a time, ended by `\n` or `\r\n` like [`crate::bytes::lines`].
+pub trait LineSource {
+    fn next_line(&mut self) -> Option<&[u8]>;
+}
+
+/// The lines of a whole input, so that a streaming part also runs on one with
+/// `stream(&mut input::lines(input))`.
+pub fn lines(input: &[u8]) -> impl LineSource + '_ {
+    Lines(bytes::lines(input), PhantomData)
+}
+
+// The lifetime says the lines outlive any borrow of the iterator
+struct Lines<'a, I>(I, PhantomData<&'a [u8]>);
+
+impl<'a, I: Iterator<Item = &'a [u8]>> LineSource for Lines<'a, I> {
+    fn next_line(&mut self) -> Option<&[u8]> {
+        self.0.next()
+    }
+}
+
+/// Lines read from `R` into one reused buffer.
+pub struct Stream<R> {
+    reader: R,
+    line: Vec<u8>,
+}
+
+impl Stream<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Stream::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: BufRead> Stream<R> {
+    pub fn new(reader: R) -> Self {
+        Stream {
+            reader,
+            line: Vec::new(),
+        }
+    }
+}
+
+impl<R: BufRead> LineSource for Stream<R> {
+    /// Panics if the input can't be read anymore, like the runner does when it can't
+    /// read the whole input.
+    fn next_line(&mut self) -> Option<&[u8]> {
+        self.line.clear();
+        let read = self
+            .reader
+            .read_until(b'\n', &mut self.line)
+            .expect("Failed to read file");
+        if read == 0 {
+            return None;
+        }
+        let line = self.line.strip_suffix(b"\n").unwrap_or(&self.line);
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn collect(mut lines: impl LineSource) -> Vec<Vec<u8>> {
+        let mut collected = Vec::new();
+        while let Some(line) = lines.next_line() {
+            collected.push(line.to_vec());
+        }
+        collected
+    }
+
+    #[test]
+    fn input_stream() {
+        for input in ["a\r\nb\n\nc\n", "a\nb", "", "\n", "x\n\n"] {
+            let streamed = collect(Stream::new(input.as_bytes()));
+            assert_eq!(streamed, collect(lines(input.as_bytes())), "{input:?}");
+            let expected: Vec<_> = input.lines().map(|l| l.as_bytes().to_vec()).collect();
+            assert_eq!(streamed, expected);
+        }
+    }
+
+    #[test]
+    fn input_source() {
+        let path = std::env::temp_dir().join(format!("aoc25-input-{}.txt", std::process::id()));
+        std::fs::write(&path, "L68\nR30\n").unwrap();
+        assert_eq!(Source::map(&path).unwrap().bytes(), b"L68\nR30\n");
+        assert_eq!(Source::read(&path).unwrap().bytes(), b"L68\nR30\n");
+        assert_eq!(collect(Stream::open(&path).unwrap()).len(), 2);
+
+        // Only checked when taken as text
+        std::fs::write(&path, [0xff, b'\n']).unwrap();
+        let source = Source::map(&path).unwrap();
+        assert_eq!(Input::new(source.bytes()).bytes(), [0xff, b'\n']);
+        let text = std::panic::catch_unwind(|| Input::new(source.bytes()).text().len());
+        assert!(text.is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn input_text() {
+        let input = Input::new(b"L68");
+        assert_eq!(input.text(), "L68");
+        assert_eq!(Input::from("L68").bytes(), b"L68");
+    }
+}
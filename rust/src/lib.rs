@@ -1,24 +1,86 @@
+pub mod alloc;
+pub mod answer;
+pub mod automaton;
+pub mod bits;
+pub mod budget;
+pub mod bytes;
+pub mod cache;
+#[cfg(test)]
+pub mod check;
+pub mod days;
+pub mod dial;
+pub mod examples;
+#[cfg(test)]
+pub mod fuzz;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod num;
+pub mod ocr;
+pub mod par;
+pub mod parse;
+pub mod runner;
+pub mod season;
+pub mod visualize;
+
+pub use answer::Answer;
+
+/// The `main` of a day's binary, running the parts declared in its module:
+/// `aoc!(day1)` runs [`days::day1::PARTS`].
 #[macro_export]
 macro_rules! aoc {
-    ($($call:ident),*) => {
+    ($day:ident) => {
         fn main() {
-            use std::io::prelude::*;
-            let bin_name = env!("CARGO_BIN_NAME");
-            let file_input_name = format!("inputs/{bin_name}.txt");
-            let mut file_input = std::fs::File::open(file_input_name).expect("Failed to open file");
-            let mut input = String::new();
-            file_input.read_to_string(&mut input).expect("Failed to read file");
-            $(
-                let before = std::time::Instant::now();
-                let result = $call(&input);
-                let after = std::time::Instant::now();
-                let delta = after - before;
-                println!("{}:", stringify!($call));
-                println!("{result}");
-                println!("---");
-                println!("{delta:?}");
-                println!("");
-            )*
+            $crate::runner::main(stringify!($day), $crate::days::$day::PARTS);
+        }
+    };
+}
+
+/// The `&[Part]` running each of the given functions, see [`runner::Part`].
+///
+/// A part can be given a time budget in milliseconds with `part_one < 5 ms`, see
+/// [`budget`]. A part that only needs one pass over the lines can also be run from
+/// a stream of them with `part_one streaming stream_one`, see [`input`].
+#[macro_export]
+macro_rules! parts {
+    (@budget) => {
+        None
+    };
+    (@budget $ms:literal) => {
+        Some(std::time::Duration::from_millis($ms))
+    };
+    (@stream) => {
+        None
+    };
+    (@stream $stream:ident) => {
+        Some(|lines| $stream(lines).into())
+    };
+    ($($call:ident $(streaming $stream:ident)? $(< $ms:literal ms)?),*) => {
+        &[$(
+            $crate::runner::Part {
+                name: stringify!($call),
+                run: |input| $call($crate::runner::FromInput::from_input(input)).into(),
+                stream: $crate::parts!(@stream $($stream)?),
+                budget: $crate::parts!(@budget $($ms)?),
+            },
+        )*]
+    };
+}
+
+/// Tests generated from the day's `examples/` files, see [`examples`].
+#[macro_export]
+macro_rules! examples {
+    ($day:ident) => {
+        #[cfg(test)]
+        mod examples {
+            use super::*;
+
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/examples/",
+                stringify!($day),
+                ".rs"
+            ));
         }
     };
 }
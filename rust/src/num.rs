@@ -0,0 +1,9 @@
+//! Arbitrary precision integers, for the answers that don't fit a machine integer.
+//!
+//! Parts are free to return a [`BigInt`] or [`BigUint`], it makes the same
+//! [`crate::Answer`] as any other integer. Days whose answers grow with the input,
+//! like day6's products or day7's timelines, compute them as big integers rather than
+//! wrapping around in release builds. Overflow checks (`cargo run --profile checked`,
+//! release optimizations with overflow checks) catch the arithmetic left unchecked:
+//! the part panics, and the runner reports it as an error.
+pub use num_bigint::{BigInt, BigUint};
@@ -0,0 +1,195 @@
+//! Reading block letters drawn with `#` (lit) and `.`, as some puzzles answer.
+//!
+//! Two fonts are known: the small one, 6 rows high with letters about 4 wide, and the
+//! large one, 10 rows high with letters 6 wide. Letters are told apart by the blank
+//! columns between them, blank rows around the text are ignored.
+use crate::grid::Grid;
+
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Reads text drawn with `#`, anything else being blank. `None` if any letter isn't
+/// one of the font's.
+pub fn read(art: &str) -> Option<String> {
+    let lines: Vec<&[u8]> = art.lines().map(str::as_bytes).collect();
+    let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
+    let mut grid = Grid::new(width, lines.len(), false);
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.iter().enumerate() {
+            grid[(x, y)] = *c == b'#';
+        }
+    }
+    read_grid(&grid)
+}
+
+/// Same as [`read`], from lit cells.
+pub fn read_grid(grid: &Grid<bool>) -> Option<String> {
+    let lit_row = |y: usize| (0..grid.width()).any(|x| grid[(x, y)]);
+    let top = (0..grid.height()).find(|y| lit_row(*y))?;
+    let bottom = (0..grid.height()).rfind(|y| lit_row(*y))?;
+    let font = match bottom - top + 1 {
+        6 => SMALL,
+        10 => LARGE,
+        _ => return None,
+    };
+
+    let lit_column = |x: usize| (top..=bottom).any(|y| grid[(x, y)]);
+    let mut text = String::new();
+    let mut x = 0;
+    while x < grid.width() {
+        if !lit_column(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < grid.width() && lit_column(x) {
+            x += 1;
+        }
+        let glyph: Vec<String> = (top..=bottom)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if grid[(x, y)] { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let glyph = glyph.join("\n");
+        let (letter, _) = font.iter().find(|(_, drawn)| *drawn == glyph)?;
+        text.push(*letter);
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// Draws `text` the way puzzles do, letters one blank column apart in the small
+    /// font, two in the large one.
+    fn draw(font: &[(char, &str)], text: &str, gap: usize) -> String {
+        let glyphs: Vec<Vec<&str>> = text
+            .chars()
+            .map(|c| {
+                let (_, drawn) = font.iter().find(|(l, _)| *l == c).unwrap();
+                drawn.lines().collect()
+            })
+            .collect();
+        let rows = glyphs[0].len();
+        (0..rows)
+            .map(|y| {
+                let row: Vec<&str> = glyphs.iter().map(|g| g[y]).collect();
+                row.join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn ocr_fonts() {
+        for (font, gap) in [(SMALL, 1), (LARGE, 2)] {
+            let alphabet: String = font.iter().map(|(c, _)| *c).collect();
+            assert_eq!(
+                read(&draw(font, &alphabet, gap)).as_deref(),
+                Some(&*alphabet)
+            );
+        }
+    }
+
+    #[test]
+    fn ocr_framed() {
+        let art = draw(SMALL, "HI", 1);
+        let framed = format!("......\n{}\n", art.replace('\n', "..\n"));
+        assert_eq!(read(&framed).as_deref(), Some("HI"));
+
+        let grid = Grid::parse(&draw(LARGE, "AZ", 2), |c| c == '#');
+        assert_eq!(read_grid(&grid).as_deref(), Some("AZ"));
+    }
+
+    #[test]
+    fn ocr_unknown() {
+        assert_eq!(read("#..#\n.##."), None);
+        let smudged = draw(SMALL, "AB", 1).replacen('.', "#", 1);
+        assert_eq!(read(&smudged), None);
+        assert_eq!(read("...\n..."), None);
+    }
+}
@@ -0,0 +1,63 @@
+//! Per-item loops that run on rayon's thread pool with the `parallel` feature, and
+//! sequentially otherwise.
+//!
+//! Results keep the order of the items, so answers don't depend on the scheduling.
+//! With a single thread (`--threads 1`, or in [`sequential`]) the plain sequential
+//! path runs, even with the feature on.
+
+pub fn map<T, U, F>(items: &[T], f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if rayon::current_num_threads() > 1 {
+        use rayon::prelude::*;
+        return items.par_iter().map(f).collect();
+    }
+
+    items.iter().map(f).collect()
+}
+
+/// Sizes the global thread pool, has to happen before the first parallel loop.
+pub fn set_threads(threads: usize) {
+    #[cfg(feature = "parallel")]
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .expect("Failed to build the thread pool");
+
+    #[cfg(not(feature = "parallel"))]
+    if threads > 1 {
+        eprintln!("--threads {threads} ignored, build with the `parallel` feature");
+    }
+}
+
+/// Runs `f` with every [`map`] on the sequential path.
+pub fn sequential<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    #[cfg(feature = "parallel")]
+    {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .expect("Failed to build the thread pool");
+        pool.install(f)
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    f()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn par_map() {
+        let items: Vec<u64> = (0..1000).collect();
+        let squares = map(&items, |x| x * x);
+        assert_eq!(squares, sequential(|| map(&items, |x| x * x)));
+        assert_eq!(squares[999], 998001);
+    }
+}
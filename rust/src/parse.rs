@@ -0,0 +1,34 @@
+use std::fmt;
+
+/// Why an input couldn't be parsed, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based, 0 when it's about the input as a whole
+    pub line: usize,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, reason: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            reason: reason.into(),
+        }
+    }
+
+    /// For errors found on the `idx`-th line of an `enumerate()`d `lines()`.
+    pub fn at(idx: usize, reason: impl Into<String>) -> Self {
+        Self::new(idx + 1, reason)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            0 => write!(f, "{}", self.reason),
+            line => write!(f, "line {line}: {}", self.reason),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
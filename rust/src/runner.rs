@@ -0,0 +1,289 @@
+//! What the days' binaries run, see [`crate::aoc!`].
+use std::{
+    any::Any,
+    panic::AssertUnwindSafe,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc, budget, cache,
+    input::{Input, LineSource, Source, Stream},
+    par, visualize, Answer,
+};
+
+pub struct Part {
+    pub name: &'static str,
+    pub run: fn(&Input) -> Answer,
+    /// The same part, reading the input one line at a time
+    pub stream: Option<fn(&mut dyn LineSource) -> Answer>,
+    pub budget: Option<Duration>,
+}
+
+/// What a part can take its input as: `&str`, or `&[u8]` for the parsers working on
+/// bytes (see [`crate::bytes`]), which don't need it checked to be UTF-8.
+pub trait FromInput<'a> {
+    fn from_input(input: &Input<'a>) -> Self;
+}
+
+impl<'a> FromInput<'a> for &'a str {
+    fn from_input(input: &Input<'a>) -> Self {
+        input.text()
+    }
+}
+
+impl<'a> FromInput<'a> for &'a [u8] {
+    fn from_input(input: &Input<'a>) -> Self {
+        input.bytes()
+    }
+}
+
+pub struct Day {
+    pub name: &'static str,
+    pub parts: &'static [Part],
+}
+
+/// The message a part panicked with.
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(msg) => msg.to_string(),
+            Err(_) => "unknown".to_string(),
+        },
+    }
+}
+
+/// Runs every part on `inputs/<day>.txt`, printing answers and timings, exits with 1
+/// when one of them failed.
+pub fn main(day: &str, parts: &[Part]) {
+    let path = format!("inputs/{day}.txt");
+    if !run(day, parts, &path, &Options::from_args()) {
+        std::process::exit(1);
+    }
+}
+
+/// Runs every part on the input at `path`, returns whether they all went fine.
+///
+/// A part panicking (overflowing with `--profile checked`, say) is reported as an error,
+/// the other parts still run.
+pub fn run(day: &str, parts: &[Part], path: &str, options: &Options) -> bool {
+    let streamed = |part: &Part| part.stream.filter(|_| options.stream);
+    // Only loaded when some part needs all of it
+    let source = parts.iter().any(|part| streamed(part).is_none()).then(|| {
+        let source = if options.mmap {
+            Source::map(path)
+        } else {
+            Source::read(path)
+        };
+        source.expect("Failed to read file")
+    });
+    let input = source.as_ref().map(|source| Input::new(source.bytes()));
+
+    let visualize = &options.visualize;
+    cache::set_capacity(options.cache_capacity);
+    if let Some(threads) = options.threads {
+        par::set_threads(threads);
+    }
+
+    let multiplier = budget::multiplier(options.budget_multiplier);
+    let mut failed = false;
+    for part in parts {
+        // Each part starts from cold caches
+        cache::reset_all();
+        visualize::start(visualize);
+        let before = Instant::now();
+        let run = || match (streamed(part), &input) {
+            (Some(stream), _) => stream(&mut Stream::open(path).expect("Failed to open file")),
+            (None, Some(input)) => (part.run)(input),
+            (None, None) => unreachable!("the input is loaded for parts that aren't streamed"),
+        };
+        let (result, allocations) =
+            alloc::measure(|| std::panic::catch_unwind(AssertUnwindSafe(run)));
+        let after = Instant::now();
+        let delta = after - before;
+        println!("{}:", part.name);
+        let result = match result {
+            Ok(result) => result,
+            Err(payload) => {
+                let message = panic_message(payload);
+                println!("error: {message}\n");
+                visualize::finish(visualize, part.name);
+                failed = true;
+                continue;
+            }
+        };
+        println!("{result}");
+        if let Some(text) = result.ocr() {
+            println!("= {text}");
+        }
+        println!("---");
+        println!("{delta:?}");
+        if let Some(allocations) = allocations {
+            println!("{allocations}");
+        }
+        if options.cache_stats {
+            for (name, stats) in cache::used() {
+                println!("{name}: {stats}");
+            }
+        }
+        if options.budget {
+            match part.budget {
+                Some(limit) => {
+                    let check = budget::Check::new(day, part.name, delta, limit, multiplier);
+                    println!("budget: {}", check.describe());
+                    budget::record(day, part.name, &check);
+                    failed |= check.exceeded();
+                }
+                None => println!("budget: none declared"),
+            }
+        }
+        println!();
+        visualize::finish(visualize, part.name);
+    }
+    !failed
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub visualize: visualize::Mode,
+    /// `--cache-cap <entries>`, only for the registered caches
+    pub cache_capacity: Option<usize>,
+    /// `--cache-stats`
+    pub cache_stats: bool,
+    /// `--threads <count>`, for the `parallel` feature
+    pub threads: Option<usize>,
+    /// `--budget`, fail when a part goes over its budget
+    pub budget: bool,
+    /// `--budget-multiplier <x>`
+    pub budget_multiplier: Option<f64>,
+    /// `--mmap`, map the input instead of reading it
+    pub mmap: bool,
+    /// `--stream`, read the input line by line for the parts that can
+    pub stream: bool,
+}
+
+impl Options {
+    pub fn from_args() -> Self {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Self {
+        let mut args = args.into_iter();
+        let mut options = Options {
+            visualize: visualize::Mode::Off,
+            cache_capacity: None,
+            cache_stats: false,
+            threads: None,
+            budget: false,
+            budget_multiplier: None,
+            mmap: false,
+            stream: false,
+        };
+        while let Some(arg) = args.next() {
+            let mut value = |usage: &str| args.next().unwrap_or_else(|| panic!("Usage: {usage}"));
+            match arg.as_str() {
+                "--visualize" => options.visualize = visualize::Mode::Terminal,
+                "--visualize-out" => {
+                    let path = value("--visualize-out <file>");
+                    options.visualize = visualize::Mode::File(path.into());
+                }
+                "--visualize-png" => {
+                    let path = value("--visualize-png <dir>");
+                    options.visualize = visualize::Mode::Png(path.into());
+                }
+                "--visualize-gif" => {
+                    let path = value("--visualize-gif <file>");
+                    options.visualize = visualize::Mode::Gif(path.into());
+                }
+                "--cache-cap" => {
+                    let cap = value("--cache-cap <entries>");
+                    let cap = cap.parse().ok().filter(|cap| *cap > 0);
+                    options.cache_capacity =
+                        Some(cap.expect("cache cap must be a positive number"));
+                }
+                "--cache-stats" => options.cache_stats = true,
+                "--threads" => {
+                    let threads = value("--threads <count>");
+                    let threads = threads.parse().ok().filter(|threads| *threads > 0);
+                    options.threads = Some(threads.expect("threads must be a positive number"));
+                }
+                "--budget" => options.budget = true,
+                "--budget-multiplier" => {
+                    let multiplier = value("--budget-multiplier <x>");
+                    let multiplier = budget::parse_multiplier(&multiplier);
+                    options.budget_multiplier =
+                        Some(multiplier.expect("budget multiplier must be a positive number"));
+                }
+                "--mmap" => options.mmap = true,
+                "--stream" => options.stream = true,
+                arg => panic!("Unknown option {arg}"),
+            }
+        }
+        options
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use super::*;
+    use crate::days;
+
+    fn parse(args: &str) -> Options {
+        Options::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn runner_options() {
+        let options = parse("--visualize-gif out.gif --cache-cap 100 --cache-stats --threads 4");
+        assert_eq!(options.visualize, visualize::Mode::Gif("out.gif".into()));
+        assert_eq!(options.cache_capacity, Some(100));
+        assert!(options.cache_stats);
+        assert_eq!(options.threads, Some(4));
+
+        assert_eq!(parse("").visualize, visualize::Mode::Off);
+
+        let options = parse("--budget --budget-multiplier 2.5");
+        assert!(options.budget);
+        assert_eq!(options.budget_multiplier, Some(2.5));
+
+        let options = parse("--mmap --stream");
+        assert!(options.mmap && options.stream);
+        assert!(!parse("").stream);
+    }
+
+    #[test]
+    fn runner_budget() {
+        // What the day1 binary runs
+        let declared = &days::day1::PARTS[0];
+        assert_eq!(declared.budget, Some(Duration::from_millis(5)));
+    }
+
+    #[test]
+    fn runner_stream() {
+        static STREAMED: AtomicBool = AtomicBool::new(false);
+        let parts = [Part {
+            name: "part_one",
+            run: |_| panic!("read whole"),
+            stream: Some(|lines| {
+                STREAMED.store(true, Ordering::Relaxed);
+                let mut count = 0;
+                while lines.next_line().is_some() {
+                    count += 1;
+                }
+                count.into()
+            }),
+            budget: None,
+        }];
+        let path = std::env::temp_dir().join(format!("aoc25-runner-{}.txt", std::process::id()));
+        std::fs::write(&path, "a\nb\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        assert!(run("test", &parts, path, &parse("--stream")));
+        assert!(STREAMED.load(Ordering::Relaxed));
+        // Without it the whole input is read
+        assert!(!run("test", &parts, path, &parse("")));
+        std::fs::remove_file(path).unwrap();
+    }
+}
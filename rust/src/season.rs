@@ -0,0 +1,519 @@
+//! Runs every day at once, see the `season` binary.
+//!
+//! Each day runs in its own process against `inputs/<day>.txt` (the binary running
+//! itself with `--day <day>`), days without an input are skipped. A panicking part is
+//! reported as such, and a day's process dying outside of a part (aborting on a stack
+//! overflow, say) as an error, without taking the other days with it. A day still
+//! running after the timeout (`--timeout <secs>`, 10 by default) is killed. Answers
+//! are checked against `inputs/<day>.expected` when there is one, in the same
+//! `part_one: <answer>` format as the examples. Art answers are stored as the letters
+//! they read.
+//!
+//! With `--budget` (and `--budget-multiplier <x>`), a part going over its
+//! [`budget`](crate::budget) fails too, and every part's time is recorded in the trend
+//! file. The days run concurrently, so the budgets should leave some room for it.
+use std::{
+    io::{BufRead, BufReader, Read},
+    panic::AssertUnwindSafe,
+    path::Path,
+    process::{Child, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    budget, cache, days,
+    examples::parse_expected,
+    grid::Grid,
+    input::Input,
+    runner::{panic_message, Day, Part},
+    Answer,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Unknown,
+    Wrong(String),
+    Panic(String),
+    Timeout,
+    /// The day's process died outside of a part
+    Error(String),
+    NoInput,
+    OverBudget(Duration),
+}
+
+impl Status {
+    pub fn failed(&self) -> bool {
+        matches!(
+            self,
+            Status::Wrong(_)
+                | Status::Panic(_)
+                | Status::Timeout
+                | Status::Error(_)
+                | Status::OverBudget(_)
+        )
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Status::Ok => "ok".to_string(),
+            Status::Unknown => "?".to_string(),
+            Status::Wrong(expected) => format!("FAIL (expected {expected})"),
+            Status::Panic(msg) => format!("PANIC ({msg})"),
+            Status::Timeout => "TIMEOUT".to_string(),
+            Status::Error(msg) => format!("ERROR ({msg})"),
+            Status::NoInput => "no input".to_string(),
+            Status::OverBudget(budget) => format!("OVER BUDGET ({budget:?})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub day: &'static str,
+    pub part: &'static str,
+    pub answer: Option<Answer>,
+    pub time: Option<Duration>,
+    pub status: Status,
+}
+
+/// What a part answered, or panicked with, and how long it took. Sent by a day's
+/// process one line at a time, see [`Outcome::line`].
+#[derive(Debug, Clone, PartialEq)]
+struct Outcome {
+    result: Result<Answer, String>,
+    time: Duration,
+}
+
+const OUTCOME: &str = "outcome";
+
+impl Outcome {
+    /// `outcome <nanos> <kind> <text>`, tab separated, with the text escaped.
+    fn line(&self) -> String {
+        let (kind, text) = match &self.result {
+            Ok(Answer::Int(n)) => ("int", n.to_string()),
+            Ok(Answer::Big(n)) => ("big", n.to_string()),
+            Ok(Answer::Text(text)) => ("text", text.clone()),
+            Ok(art @ Answer::Art(_)) => ("art", art.to_string()),
+            Err(message) => ("panic", message.clone()),
+        };
+        let text = text
+            .replace('\\', "\\\\")
+            .replace('\n', "\\n")
+            .replace('\t', "\\t");
+        format!("{OUTCOME}\t{}\t{kind}\t{text}", self.time.as_nanos())
+    }
+
+    /// Reads back [`Outcome::line`], `None` for any other line.
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(4, '\t');
+        if fields.next()? != OUTCOME {
+            return None;
+        }
+        let time = Duration::from_nanos(fields.next()?.parse().ok()?);
+        let kind = fields.next()?;
+        let mut text = String::new();
+        let mut chars = fields.next()?.chars();
+        while let Some(c) = chars.next() {
+            text.push(match c {
+                '\\' => match chars.next()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    c => c,
+                },
+                c => c,
+            });
+        }
+        let result = match kind {
+            "int" => Ok(Answer::Int(text.parse().ok()?)),
+            "big" => Ok(Answer::Big(text.parse().ok()?)),
+            "text" => Ok(Answer::Text(text)),
+            "art" => Ok(Answer::Art(Grid::try_parse(&text, Ok).ok()?)),
+            "panic" => Err(text),
+            _ => return None,
+        };
+        Some(Outcome { result, time })
+    }
+}
+
+fn run_part(day: &'static str, part: &Part, input: &Input) -> Outcome {
+    cache::reset_day(day);
+    let before = Instant::now();
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| (part.run)(input).decoded()));
+    Outcome {
+        result: result.map_err(panic_message),
+        time: before.elapsed(),
+    }
+}
+
+/// `budget` is the already scaled budget of the part, if checked.
+fn check_part(
+    day: &'static str,
+    part: &Part,
+    outcome: Outcome,
+    expected: Option<&str>,
+    budget: Option<Duration>,
+) -> Row {
+    let Outcome { result, time } = outcome;
+    let (answer, status) = match result {
+        Err(message) => (None, Status::Panic(message)),
+        Ok(answer) => {
+            let status = match expected {
+                None => Status::Unknown,
+                Some(expected) if answer.matches(expected) => Status::Ok,
+                Some(expected) => Status::Wrong(expected.to_string()),
+            };
+            let status = match budget {
+                Some(budget) if !status.failed() && time > budget => Status::OverBudget(budget),
+                _ => status,
+            };
+            (Some(answer), status)
+        }
+    };
+    Row {
+        day,
+        part: part.name,
+        answer,
+        time: Some(time),
+        status,
+    }
+}
+
+fn rows_without_answer(day: &Day, status: Status) -> impl Iterator<Item = Row> + '_ {
+    day.parts.iter().map(move |part| Row {
+        day: day.name,
+        part: part.name,
+        answer: None,
+        time: None,
+        status: status.clone(),
+    })
+}
+
+/// Runs every day concurrently, each in a process of `season`, returns one row per
+/// part, in the order of [`days::ALL`].
+///
+/// Budgets are checked when given a multiplier.
+pub fn run(season: &Path, timeout: Duration, budget: Option<f64>) -> Vec<Row> {
+    let deadline = Instant::now() + timeout;
+
+    let running: Vec<_> = days::ALL
+        .iter()
+        .map(|day| {
+            let input = Path::new(&format!("inputs/{}.txt", day.name)).exists();
+            (day, input.then(|| spawn_day(season, day, budget)))
+        })
+        .collect();
+
+    let mut rows = Vec::new();
+    for (day, running) in running {
+        let Some((mut child, receive, stderr)) = running else {
+            rows.extend(rows_without_answer(day, Status::NoInput));
+            continue;
+        };
+        rows.extend(receive_rows(day, &receive, deadline, || {
+            let status = match child.wait() {
+                Ok(status) => status.to_string(),
+                Err(err) => err.to_string(),
+            };
+            // What it printed last, a panic message or why it aborted
+            let stderr = stderr.join().unwrap_or_default();
+            match stderr.lines().rev().find(|line| !line.trim().is_empty()) {
+                Some(last) => format!("{last}, {status}"),
+                None => status,
+            }
+        }));
+        // Given up on, if it's still running
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+    rows
+}
+
+/// Starts the process running `day`, with a thread turning what it answers into rows
+/// and one collecting its stderr.
+fn spawn_day(
+    season: &Path,
+    day: &'static Day,
+    budget: Option<f64>,
+) -> (Child, Receiver<Row>, JoinHandle<String>) {
+    let mut child = Command::new(season)
+        .args(["--day", day.name])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start a day");
+    let stdout = child.stdout.take().expect("piped stdout");
+    let mut stderr = child.stderr.take().expect("piped stderr");
+
+    let expected =
+        std::fs::read_to_string(format!("inputs/{}.expected", day.name)).unwrap_or_default();
+    let (send, receive) = mpsc::channel();
+    std::thread::spawn(move || {
+        let expected = parse_expected(&expected);
+        let outcomes = BufReader::new(stdout)
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| Outcome::parse(&line));
+        for (part, outcome) in day.parts.iter().zip(outcomes) {
+            let expected = expected
+                .iter()
+                .find_map(|(name, answer)| (*name == part.name).then_some(*answer));
+            let budget = budget.and_then(|multiplier| part.budget.map(|b| b.mul_f64(multiplier)));
+            let row = check_part(day.name, part, outcome, expected, budget);
+            if let (Some(budget), Some(elapsed)) = (budget, row.time) {
+                let check = budget::Check {
+                    elapsed,
+                    budget,
+                    previous: None,
+                };
+                budget::record(day.name, part.name, &check);
+            }
+            let _ = send.send(row);
+        }
+    });
+    let stderr = std::thread::spawn(move || {
+        let mut text = String::new();
+        let _ = stderr.read_to_string(&mut text);
+        text
+    });
+    (child, receive, stderr)
+}
+
+/// What a day's process does: runs the day's parts on its input, printing an
+/// [`Outcome`] line for each.
+fn run_day(name: &str) {
+    let day = days::ALL
+        .iter()
+        .find(|day| day.name == name)
+        .unwrap_or_else(|| panic!("Unknown day {name}"));
+    let input = std::fs::read(format!("inputs/{name}.txt")).expect("Failed to read file");
+    let input = Input::new(&input);
+    // One line per panic, the last one says what stopped the day if it wasn't a part
+    std::panic::set_hook(Box::new(|info| {
+        eprintln!("{}", info.payload_as_str().unwrap_or("unknown"));
+    }));
+    for part in day.parts {
+        println!("{}", run_part(day.name, part, &input).line());
+    }
+}
+
+/// The rows received for `day`, until `deadline`. The parts it didn't get to time
+/// out, or fail with what `died` says stopped the day.
+fn receive_rows(
+    day: &Day,
+    receive: &Receiver<Row>,
+    deadline: Instant,
+    died: impl FnOnce() -> String,
+) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let status = loop {
+        if rows.len() == day.parts.len() {
+            return rows;
+        }
+        let wait = deadline.saturating_duration_since(Instant::now());
+        match receive.recv_timeout(wait) {
+            Ok(row) => rows.push(row),
+            Err(RecvTimeoutError::Timeout) => break Status::Timeout,
+            Err(RecvTimeoutError::Disconnected) => break Status::Error(died()),
+        }
+    };
+    let done = rows.len();
+    rows.extend(rows_without_answer(day, status).skip(done));
+    rows
+}
+
+/// A positive number of seconds.
+fn seconds(value: Option<String>) -> Option<Duration> {
+    let secs: f64 = value?.parse().ok()?;
+    (secs.is_finite() && secs > 0.0).then(|| Duration::from_secs_f64(secs))
+}
+
+pub fn report(rows: &[Row], total: Duration) -> String {
+    let answer = |row: &Row| {
+        let answer = row
+            .answer
+            .as_ref()
+            .map_or("-".to_string(), Answer::to_string);
+        match answer.split_once('\n') {
+            Some((first, _)) => format!("{first} ..."),
+            None => answer,
+        }
+    };
+    let time = |row: &Row| row.time.map_or("-".to_string(), |t| format!("{t:.2?}"));
+    let width = rows
+        .iter()
+        .map(|r| answer(r).len())
+        .max()
+        .unwrap_or(0)
+        .max(6);
+
+    let mut out = format!(
+        "{:<6} {:<10} {:<width$} {:>12}  status\n",
+        "day", "part", "answer", "time"
+    );
+    for row in rows {
+        out.push_str(&format!(
+            "{:<6} {:<10} {:<width$} {:>12}  {}\n",
+            row.day,
+            row.part,
+            answer(row),
+            time(row),
+            row.status.describe(),
+        ));
+    }
+
+    let sum: Duration = rows.iter().filter_map(|r| r.time).sum();
+    let failed = rows.iter().filter(|r| r.status.failed()).count();
+    out.push_str(&format!(
+        "\ntotal {total:.2?} ({sum:.2?} summed over parts), {failed} failed\n"
+    ));
+    out
+}
+
+pub fn main() {
+    let mut args = std::env::args().skip(1);
+    let mut timeout = TIMEOUT;
+    let mut check_budget = false;
+    let mut multiplier = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => return run_day(&args.next().expect("Usage: --day <day>")),
+            "--timeout" => {
+                timeout = seconds(args.next()).expect("Usage: --timeout <secs>, more than 0");
+            }
+            "--budget" => check_budget = true,
+            "--budget-multiplier" => {
+                let m = args.next().and_then(|m| budget::parse_multiplier(&m));
+                multiplier = Some(m.expect("Usage: --budget-multiplier <x>, more than 0"));
+            }
+            arg => panic!("Unknown option {arg}"),
+        }
+    }
+
+    let season = std::env::current_exe().expect("Failed to find the season binary");
+    let before = Instant::now();
+    let budget = check_budget.then(|| budget::multiplier(multiplier));
+    let rows = run(&season, timeout, budget);
+    print!("{}", report(&rows, before.elapsed()));
+
+    if rows.iter().any(|row| row.status.failed()) {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    static BROKEN: &[Day] = &[Day {
+        name: "broken",
+        parts: &[Part {
+            name: "part_one",
+            run: |_| panic!("oops"),
+            stream: None,
+            budget: None,
+        }],
+    }];
+
+    fn run_checked(part: &Part, expected: Option<&str>, budget: Option<Duration>) -> Row {
+        let outcome = run_part("test", part, &"abc".into());
+        check_part("test", part, outcome, expected, budget)
+    }
+
+    #[test]
+    fn season_report() {
+        let part = Part {
+            name: "part_one",
+            run: |input| input.bytes().len().into(),
+            stream: None,
+            budget: None,
+        };
+        let ok = run_checked(&part, Some("3"), None);
+        assert_eq!(ok.status, Status::Ok);
+        let wrong = run_checked(&part, Some("4"), None);
+        assert_eq!(wrong.status, Status::Wrong("4".to_string()));
+
+        let report = report(&[ok, wrong], Duration::from_millis(1));
+        assert!(report.contains("FAIL (expected 4)"));
+        assert!(report.contains("1 failed"));
+    }
+
+    #[test]
+    fn season_panic() {
+        let row = run_checked(&BROKEN[0].parts[0], None, None);
+        assert_eq!(row.status, Status::Panic("oops".to_string()));
+    }
+
+    #[test]
+    fn season_outcome_line() {
+        let art = Answer::Art(Grid::parse("#.\n.#", |c| c));
+        let results = [
+            Ok(Answer::Int(-7)),
+            Ok(Answer::from(u128::MAX)),
+            Ok(Answer::Text("007\tand \\n\n".to_string())),
+            Ok(art),
+            Err("overflowed\non two lines".to_string()),
+        ];
+        for result in results {
+            let outcome = Outcome {
+                result,
+                time: Duration::from_nanos(1234),
+            };
+            let line = outcome.line();
+            assert!(!line.contains('\n'), "{line:?}");
+            assert_eq!(Outcome::parse(&line), Some(outcome));
+        }
+        assert_eq!(Outcome::parse("printed by a part"), None);
+    }
+
+    #[test]
+    fn season_day_died() {
+        let (send, receive) = mpsc::channel();
+        send.send(run_checked(&BROKEN[0].parts[0], None, None))
+            .unwrap();
+        drop(send);
+        // Any day with two parts
+        let day = &days::ALL[0];
+        assert_eq!(day.parts.len(), 2);
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let rows = receive_rows(day, &receive, deadline, || "stack overflow".to_string());
+        assert_eq!(rows[0].status, Status::Panic("oops".to_string()));
+        assert_eq!(rows[1].status, Status::Error("stack overflow".to_string()));
+
+        let (_send, receive) = mpsc::channel();
+        let rows = receive_rows(day, &receive, Instant::now(), || unreachable!());
+        assert_eq!(rows[1].status, Status::Timeout);
+    }
+
+    #[test]
+    fn season_timeout() {
+        let secs = |s: &str| seconds(Some(s.to_string()));
+        assert_eq!(secs("1.5"), Some(Duration::from_millis(1500)));
+        for invalid in ["-1", "NaN", "inf", "0", "x"] {
+            assert_eq!(secs(invalid), None, "{invalid}");
+        }
+        assert_eq!(seconds(None), None);
+    }
+
+    #[test]
+    fn season_budget() {
+        let part = Part {
+            name: "part_one",
+            run: |input| {
+                std::thread::sleep(Duration::from_millis(20));
+                input.bytes().len().into()
+            },
+            stream: None,
+            budget: None,
+        };
+        let slow = run_checked(&part, Some("3"), Some(Duration::from_millis(1)));
+        assert_eq!(slow.status, Status::OverBudget(Duration::from_millis(1)));
+        assert!(slow.status.failed());
+        let fine = run_checked(&part, None, Some(Duration::from_secs(10)));
+        assert_eq!(fine.status, Status::Unknown);
+    }
+}
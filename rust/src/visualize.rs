@@ -0,0 +1,204 @@
+//! Frames emitted by solutions, played back by the `aoc!` runner.
+//!
+//! Run a day with `--visualize` to play its frames in the terminal, or with
+//! `--visualize-out <file>` to dump them as text. `--visualize-png <dir>` and
+//! `--visualize-gif <file>` render them as images instead. Recording is off otherwise,
+//! and [`emit`] doesn't even build the frame.
+//!
+//! Frames are recorded by the thread that called [`start`], the one running the part.
+use std::{
+    cell::{Cell, RefCell},
+    io::Write,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use crate::grid::{Grid, Pos};
+
+pub mod image;
+
+const FRAME_DELAY: Duration = Duration::from_millis(80);
+const HIGHLIGHT: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+thread_local! {
+    static RECORDING: Cell<bool> = const { Cell::new(false) };
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+}
+static FILE_STARTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub caption: String,
+    pub grid: Grid<char>,
+    pub highlights: Vec<Pos>,
+    /// Per cell intensity in `0.0..=1.0`, only used by the image outputs.
+    pub heat: Option<Grid<f32>>,
+}
+
+impl Frame {
+    pub fn new(caption: impl Into<String>, grid: Grid<char>) -> Self {
+        Frame {
+            caption: caption.into(),
+            grid,
+            highlights: Vec::new(),
+            heat: None,
+        }
+    }
+
+    pub fn highlight(mut self, highlights: impl IntoIterator<Item = Pos>) -> Self {
+        self.highlights.extend(highlights);
+        self
+    }
+
+    pub fn heat(mut self, heat: Grid<f32>) -> Self {
+        self.heat = Some(heat);
+        self
+    }
+
+    pub fn render(&self, colored: bool) -> String {
+        let mut highlighted = self.grid.map(|_| false);
+        for pos in &self.highlights {
+            highlighted[*pos] = true;
+        }
+
+        let mut out = format!("{}\n", self.caption);
+        for ((x, y), c) in self.grid.iter() {
+            match colored && highlighted[(x, y)] {
+                true => out.push_str(&format!("{HIGHLIGHT}{c}{RESET}")),
+                false => out.push(*c),
+            }
+            if x + 1 == self.grid.width() {
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Off,
+    Terminal,
+    File(PathBuf),
+    Png(PathBuf),
+    Gif(PathBuf),
+}
+
+pub fn recording() -> bool {
+    RECORDING.get()
+}
+
+/// Records the frame built by `frame`, if the runner is visualizing.
+pub fn emit(frame: impl FnOnce() -> Frame) {
+    if recording() {
+        let frame = frame();
+        FRAMES.with_borrow_mut(|frames| frames.push(frame));
+    }
+}
+
+pub fn start(mode: &Mode) {
+    RECORDING.set(*mode != Mode::Off);
+}
+
+/// Stops recording and plays back or dumps the frames recorded since [`start`].
+pub fn finish(mode: &Mode, part: &str) {
+    RECORDING.set(false);
+    let frames = FRAMES.take();
+
+    match mode {
+        _ if frames.is_empty() => {}
+        Mode::Off => {}
+        Mode::Terminal => {
+            let mut stdout = std::io::stdout().lock();
+            for frame in &frames {
+                let _ = write!(stdout, "\x1b[2J\x1b[H{part}: {}", frame.render(true));
+                let _ = stdout.flush();
+                std::thread::sleep(FRAME_DELAY);
+            }
+        }
+        Mode::File(path) => {
+            // Later parts of the same run go after the first one
+            let append = FILE_STARTED.swap(true, Ordering::Relaxed);
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(append)
+                .truncate(!append)
+                .open(path)
+                .expect("Failed to open visualization file");
+            for frame in &frames {
+                writeln!(file, "{part}: {}", frame.render(false))
+                    .expect("Failed to write visualization file");
+            }
+            println!("Wrote {} frames to {}", frames.len(), path.display());
+        }
+        Mode::Png(dir) => {
+            std::fs::create_dir_all(dir).expect("Failed to create visualization directory");
+            for (idx, frame) in frames.iter().enumerate() {
+                let path = dir.join(format!("{part}-{idx:04}.png"));
+                image::write_png(frame, &path).expect("Failed to write PNG frame");
+            }
+            println!("Wrote {} frames to {}", frames.len(), dir.display());
+        }
+        Mode::Gif(path) => {
+            // One file per part, next to the requested one
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let path = path.with_file_name(format!("{stem}-{part}.gif"));
+            image::write_gif(&frames, &path).expect("Failed to write GIF");
+            println!("Wrote {} frames to {}", frames.len(), path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn frame(caption: &str) -> Frame {
+        Frame::new(caption, Grid::parse("#.\n.#", |c| c)).highlight([(1, 1)])
+    }
+
+    #[test]
+    fn visualize_emit() {
+        emit(|| panic!("built while not recording"));
+        assert!(!recording());
+
+        start(&Mode::Terminal);
+        assert!(recording());
+        emit(|| frame("first"));
+        emit(|| frame("second"));
+        let recorded = FRAMES.with_borrow(|frames| frames.clone());
+        assert_eq!(recorded, [frame("first"), frame("second")]);
+        // Other threads aren't recording
+        assert!(!std::thread::spawn(recording).join().unwrap());
+
+        start(&Mode::Off);
+        finish(&Mode::Off, "part_one");
+        assert!(!recording());
+        assert!(FRAMES.with_borrow(|frames| frames.is_empty()));
+        emit(|| panic!("built after finishing"));
+    }
+
+    #[test]
+    fn visualize_replay() {
+        let path = std::env::temp_dir().join(format!("aoc25-frames-{}.txt", std::process::id()));
+        let mode = Mode::File(path.clone());
+        start(&mode);
+        emit(|| frame("first"));
+        emit(|| frame("second"));
+        finish(&mode, "part_one");
+
+        let replayed = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            replayed,
+            "part_one: first\n#.\n.#\n\npart_one: second\n#.\n.#\n\n"
+        );
+        assert_eq!(
+            frame("colored").render(true),
+            format!("colored\n#.\n.{HIGHLIGHT}#{RESET}\n")
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Pure Rust PNG and GIF output for [`Frame`]s.
+use std::{fs::File, io::BufWriter, path::Path};
+
+use super::Frame;
+
+/// Pixels per cell side
+const SCALE: usize = 4;
+/// In hundredths of a second
+const GIF_DELAY: u16 = 8;
+
+const BACKGROUND: [u8; 3] = [16, 16, 24];
+const FOREGROUND: [u8; 3] = [190, 190, 190];
+const HIGHLIGHT: [u8; 3] = [255, 200, 0];
+
+/// Black to red to yellow to white, for `t` in `0.0..=1.0`.
+pub fn heat_color(t: f32) -> [u8; 3] {
+    let channel = |offset: f32| ((3.0 * t - offset).clamp(0.0, 1.0) * 255.0) as u8;
+    [channel(0.0), channel(1.0), channel(2.0)]
+}
+
+/// Frame as RGB pixels, returns `(width, height, pixels)`.
+pub fn rgb(frame: &Frame) -> (usize, usize, Vec<u8>) {
+    let mut highlighted = frame.grid.map(|_| false);
+    for pos in &frame.highlights {
+        highlighted[*pos] = true;
+    }
+
+    let width = frame.grid.width() * SCALE;
+    let height = frame.grid.height() * SCALE;
+    let mut pixels = vec![0; width * height * 3];
+    for (pos, c) in frame.grid.iter() {
+        let heat = frame.heat.as_ref().map_or(0.0, |heat| heat[pos]);
+        let color = match c {
+            _ if highlighted[pos] => HIGHLIGHT,
+            _ if heat > 0.0 => heat_color(heat),
+            '.' | ' ' => BACKGROUND,
+            _ => FOREGROUND,
+        };
+
+        let (x, y) = pos;
+        for yy in y * SCALE..(y + 1) * SCALE {
+            let row = yy * width;
+            for xx in x * SCALE..(x + 1) * SCALE {
+                pixels[(row + xx) * 3..(row + xx + 1) * 3].copy_from_slice(&color);
+            }
+        }
+    }
+    (width, height, pixels)
+}
+
+pub fn write_png(frame: &Frame, path: &Path) -> Result<(), png::EncodingError> {
+    let (width, height, pixels) = rgb(frame);
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = png::Encoder::new(file, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels)
+}
+
+pub fn write_gif(frames: &[Frame], path: &Path) -> Result<(), gif::EncodingError> {
+    let Some(first) = frames.first() else {
+        return Ok(());
+    };
+    let (width, height, _) = rgb(first);
+    let too_big = || std::io::Error::other("frames are too big for a GIF");
+    let width = u16::try_from(width).map_err(|_| too_big())?;
+    let height = u16::try_from(height).map_err(|_| too_big())?;
+
+    let file = BufWriter::new(File::create(path)?);
+    let mut encoder = gif::Encoder::new(file, width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    for frame in frames {
+        let (w, h, pixels) = rgb(frame);
+        if (w, h) != (width as usize, height as usize) {
+            return Err(std::io::Error::other("all frames should be of the same size").into());
+        }
+        let mut gif_frame = gif::Frame::from_rgb_speed(width, height, &pixels, 10);
+        gif_frame.delay = GIF_DELAY;
+        encoder.write_frame(&gif_frame)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    #[test]
+    fn image_rgb() {
+        let frame = Frame::new("", Grid::parse(".#\n..", |c| c)).highlight([(0, 1)]);
+        let (width, height, pixels) = rgb(&frame);
+        assert_eq!((width, height), (2 * SCALE, 2 * SCALE));
+        assert_eq!(pixels[..3], BACKGROUND);
+        assert_eq!(pixels[SCALE * 3..SCALE * 3 + 3], FOREGROUND);
+        assert_eq!(pixels[width * SCALE * 3..width * SCALE * 3 + 3], HIGHLIGHT);
+
+        assert_eq!(heat_color(0.0), [0, 0, 0]);
+        assert_eq!(heat_color(1.0), [255, 255, 255]);
+    }
+}
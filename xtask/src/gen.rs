@@ -0,0 +1,54 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+use aoc25_generate::{self as generate, Rng};
+
+pub const USAGE: &str = "Usage: cargo xt gen <day> --size <n> [--seed <s>] [--out <file>]";
+
+/// Writes the input to stdout, or to `out` with the known answers next to it in
+/// `<out>.expected`, the format of the example files.
+pub fn gen_input(day: u32, size: usize, seed: u64, out: Option<PathBuf>) {
+    let name = format!("day{day}");
+    let generator = generate::find(&name).unwrap_or_else(|| {
+        let days: Vec<_> = generate::GENERATORS
+            .iter()
+            .map(|(day, _, _)| *day)
+            .collect();
+        panic!("No generator for {name}, only for {}", days.join(", "))
+    });
+
+    let mut rng = Rng::new(seed);
+    let known = match &out {
+        Some(path) => {
+            let file = File::create(path).expect("Failed to create the input file");
+            let mut file = BufWriter::new(file);
+            let known = generator(size, &mut rng, &mut file).expect("Failed to write input");
+            file.flush().expect("Failed to write input");
+            known
+        }
+        None => {
+            let mut stdout = BufWriter::new(std::io::stdout().lock());
+            let known = generator(size, &mut rng, &mut stdout).expect("Failed to write input");
+            stdout.flush().expect("Failed to write input");
+            known
+        }
+    };
+
+    let expected: String = known
+        .iter()
+        .map(|(part, answer)| format!("{part}: {answer}\n"))
+        .collect();
+    match out {
+        Some(path) => {
+            if !known.is_empty() {
+                let expected_path = path.with_extension("expected");
+                std::fs::write(&expected_path, expected).expect("Failed to write answers");
+                println!("Known answers in {}", expected_path.display());
+            }
+            println!("Successfully generated {}", path.display());
+        }
+        // Out of the way of the input
+        None => eprint!("{expected}"),
+    }
+}
@@ -1,22 +1,43 @@
 use dotenv::dotenv;
 
+mod gen;
 mod get;
 
 pub const YEAR: u32 = 2025;
 
 fn main() {
-    dotenv().expect("Failed to load .env");
     let mut args = std::env::args();
     let task = args.nth(1);
     match task.as_deref() {
         Some("get") => {
+            dotenv().expect("Failed to load .env");
             let day = args.next().expect("Usage: cargo xt get <day>");
             let day = day.parse().expect("day must be a positive number");
             get::get_input(day)
         }
+        Some("gen") => {
+            let day = args.next().expect(gen::USAGE);
+            let day = day.parse().expect("day must be a positive number");
+            let (mut size, mut seed, mut out) = (None, 0, None);
+            while let Some(arg) = args.next() {
+                let value = args.next().expect(gen::USAGE);
+                match arg.as_str() {
+                    "--size" => size = Some(value.parse().expect("size must be a number")),
+                    "--seed" => seed = value.parse().expect("seed must be a number"),
+                    "--out" => out = Some(value.into()),
+                    _ => panic!("{}", gen::USAGE),
+                }
+            }
+            gen::gen_input(day, size.expect(gen::USAGE), seed, out)
+        }
         Some(_) | None => {
             println!("Available commands:");
             println!("get <day(u32)>       Gets the input for the provided day");
+            println!("gen <day(u32)> --size <n> [--seed <s>] [--out <file>]");
+            println!("                     Generates an input, with its answers when known");
+            for (day, size, _) in aoc25_generate::GENERATORS {
+                println!("                     {day}: size is the number of {size}");
+            }
         }
     }
 }